@@ -18,7 +18,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .get_day_ahead_prices(BiddingZone::FI, start, end)
         .await?;
 
-    println!("{:#?}", price_doc);
+    println!("{price_doc}");
     println!();
     println!("Note: API returns prices in EUR/MWh");
     println!(
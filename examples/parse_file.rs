@@ -0,0 +1,78 @@
+//! Parses a day-ahead price XML file offline, e.g. one downloaded from the ENTSO-E web UI.
+//!
+//! Usage:
+//!   cargo run --example parse_file -- prices.xml            # Pretty-print the PriceDocument
+//!   cargo run --example parse_file -- prices.xml --csv FI   # CSV, as entsoe-csv writes it
+
+use entsoe::{BiddingZone, parse_day_ahead_prices};
+use std::env;
+use std::io::{self, Write};
+
+/// Parses `xml` and writes the document to `out`, as CSV for `csv_zone` if given.
+fn render(
+    xml: &[u8],
+    csv_zone: Option<BiddingZone>,
+    mut out: impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let doc = parse_day_ahead_prices(xml)?;
+    match csv_zone {
+        Some(zone) => doc.write_csv(zone, out)?,
+        None => writeln!(out, "{doc:#?}")?,
+    }
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (path, csv_zone) = match args.as_slice() {
+        [path] => (path, None),
+        [path, flag, zone] if flag == "--csv" => {
+            let zone = BiddingZone::from_code(zone)
+                .ok_or_else(|| format!("Invalid bidding zone: '{zone}'"))?;
+            (path, Some(zone))
+        }
+        _ => return Err("Usage: parse_file <file.xml> [--csv ZONE]".into()),
+    };
+
+    let xml = std::fs::read(path)?;
+    render(&xml, csv_zone, io::stdout().lock())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &[u8] = include_bytes!("../tests/fixtures/day_ahead_prices_fi.xml");
+
+    #[test]
+    fn test_render_debug() {
+        let mut out = Vec::new();
+        render(FIXTURE, None, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("PriceDocument {"));
+        assert!(text.contains("resolution: PT15M"));
+    }
+
+    #[test]
+    fn test_render_csv() {
+        let mut out = Vec::new();
+        render(FIXTURE, Some(BiddingZone::FI), &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let mut lines = text.lines();
+        assert_eq!(
+            lines.next(),
+            Some("timestamp,price_per_kwh,currency,price_area")
+        );
+        let first = lines.next().unwrap();
+        assert!(first.ends_with(",EUR,FI"), "{first}");
+        assert_eq!(
+            text.lines().count(),
+            parse_day_ahead_prices(FIXTURE).unwrap().prices.len() + 1
+        );
+    }
+
+    #[test]
+    fn test_render_invalid_xml() {
+        assert!(render(b"<html></html>", None, Vec::new()).is_err());
+    }
+}
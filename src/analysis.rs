@@ -0,0 +1,591 @@
+//! Analysis helpers that work across price documents and user-supplied series.
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+use std::iter::Sum;
+use std::ops::{Add, Div, Sub};
+
+use chrono::{DateTime, Timelike, Utc};
+use chrono_tz::Tz;
+use rust_decimal::Decimal;
+
+use crate::bidding_zone::BiddingZone;
+use crate::models::PriceDocument;
+use crate::models::price::{Price, price_to_f64};
+
+/// Consumer price in ¢/kWh for a spot price in ¢/kWh.
+///
+/// Computed as `(spot + margin) * (1 + vat / 100) + transfer`: VAT (in percent) applies to the
+/// spot price and the retailer margin, while `transfer` is added as given and so should already
+/// include any tax on the grid fee. Negative spot prices are taxed the same way.
+#[must_use]
+pub fn apply_consumer_price(
+    spot_cents: Decimal,
+    vat_percent: Decimal,
+    margin_cents: Decimal,
+    transfer_cents: Decimal,
+) -> Decimal {
+    (spot_cents + margin_cents) * (Decimal::ONE + vat_percent / Decimal::ONE_HUNDRED)
+        + transfer_cents
+}
+
+/// Price in EUR/MWh at `now` for every zone, sorted by zone code.
+///
+/// Zones whose document has no point covering `now` map to `None`.
+#[must_use]
+pub fn current_prices<S: BuildHasher>(
+    docs: &HashMap<BiddingZone, PriceDocument, S>,
+    now: DateTime<Utc>,
+) -> Vec<(BiddingZone, Option<Price>)> {
+    let mut prices: Vec<(BiddingZone, Option<Price>)> = docs
+        .iter()
+        .map(|(zone, doc)| (*zone, doc.price_at_instant(now)))
+        .collect();
+    prices.sort_by_key(|(zone, _)| zone.code());
+    prices
+}
+
+/// Zones ranked from cheapest to most expensive by their price in EUR/MWh at `at`.
+///
+/// Zones whose document has no point covering `at` are left out. Equal prices are ordered by
+/// zone code.
+#[must_use]
+pub fn rank_zones_at<S: BuildHasher>(
+    docs: &HashMap<BiddingZone, PriceDocument, S>,
+    at: DateTime<Utc>,
+) -> Vec<(BiddingZone, f64)> {
+    let mut ranking: Vec<(BiddingZone, f64)> = docs
+        .iter()
+        .filter_map(|(zone, doc)| Some((*zone, price_to_f64(doc.price_at_instant(at)?))))
+        .collect();
+    ranking.sort_by(|a, b| a.1.total_cmp(&b.1).then_with(|| a.0.code().cmp(b.0.code())));
+    ranking
+}
+
+/// Which end of the ranking [`rank_windows`] starts from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowOrder {
+    /// Lowest average first.
+    Cheapest,
+    /// Highest average first.
+    MostExpensive,
+}
+
+/// The `top_k` cheapest (or most expensive) runs of `n` consecutive prices, ranked by `order`.
+///
+/// Returns each window's start index and average price. Windows with equal averages keep time
+/// order. With `non_overlapping`, windows are picked greedily from the top of the ranking and any
+/// window sharing a period with one already picked is skipped, so fewer than `top_k` may be
+/// returned. Returns an empty vector if `n` is zero or longer than `prices`.
+///
+/// Works on any price type, e.g. [`Price`] or `Decimal` display prices, and backs
+/// [`PriceDocument::cheapest_window`] and [`PriceDocument::most_expensive_window`].
+#[must_use]
+pub fn rank_windows<P>(
+    prices: &[P],
+    n: usize,
+    top_k: usize,
+    non_overlapping: bool,
+    order: WindowOrder,
+) -> Vec<(usize, P)>
+where
+    P: Copy + PartialOrd + Sum + Add<Output = P> + Sub<Output = P> + Div<Output = P> + From<u32>,
+{
+    let Some(count) = u32::try_from(n)
+        .ok()
+        .filter(|_| n > 0 && n <= prices.len())
+        .map(P::from)
+    else {
+        return Vec::new();
+    };
+
+    let mut sum: P = prices[..n].iter().copied().sum();
+    let mut windows = vec![(0, sum / count)];
+    for i in n..prices.len() {
+        sum = sum + prices[i] - prices[i - n];
+        windows.push((i + 1 - n, sum / count));
+    }
+    // Stable sort keeps ties in time order
+    windows.sort_by(|a, b| {
+        let cheaper_first = a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal);
+        match order {
+            WindowOrder::Cheapest => cheaper_first,
+            WindowOrder::MostExpensive => cheaper_first.reverse(),
+        }
+    });
+
+    if !non_overlapping {
+        windows.truncate(top_k);
+        return windows;
+    }
+
+    let mut picked: Vec<(usize, P)> = Vec::new();
+    for (start, avg) in windows {
+        if picked.len() == top_k {
+            break;
+        }
+        if picked
+            .iter()
+            .all(|&(other, _)| start + n <= other || other + n <= start)
+        {
+            picked.push((start, avg));
+        }
+    }
+    picked
+}
+
+/// Lorenz-style cost distribution of a consumption profile.
+///
+/// `consumption` maps period start timestamps to kWh consumed in that period. Periods are ordered
+/// from cheapest to most expensive and the result holds cumulative (consumption fraction, cost
+/// fraction) pairs, starting at `(0.0, 0.0)` and ending at `(1.0, 1.0)`. Consumption without a
+/// matching price point is ignored.
+///
+/// Returns an empty vector when there is no matched consumption or the total cost is not positive.
+#[must_use]
+pub fn cost_distribution(
+    doc: &PriceDocument,
+    consumption: &BTreeMap<DateTime<Utc>, f64>,
+) -> Vec<(f64, f64)> {
+    let mut periods: Vec<(f64, f64, f64)> = doc
+        .prices
+        .iter()
+        .filter_map(|point| {
+            let kwh = *consumption.get(&point.timestamp)?;
+            let price = price_to_f64(point.price);
+            Some((price, kwh, kwh * price / 1000.0))
+        })
+        .collect();
+
+    let total_kwh: f64 = periods.iter().map(|(_, kwh, _)| kwh).sum();
+    let total_cost: f64 = periods.iter().map(|(_, _, cost)| cost).sum();
+    if total_kwh <= 0.0 || total_cost <= 0.0 {
+        return Vec::new();
+    }
+
+    periods.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut curve = Vec::with_capacity(periods.len() + 1);
+    curve.push((0.0, 0.0));
+    let (mut cumulative_kwh, mut cumulative_cost) = (0.0, 0.0);
+    for (_, kwh, cost) in periods {
+        cumulative_kwh += kwh;
+        cumulative_cost += cost;
+        curve.push((cumulative_kwh / total_kwh, cumulative_cost / total_cost));
+    }
+    curve
+}
+
+/// Mean of `doc price - reference` in EUR/MWh over timestamps present in both.
+///
+/// `reference` maps period start timestamps to EUR/MWh, e.g. a hub index. Returns `None` if no
+/// timestamp matches.
+#[must_use]
+pub fn basis(doc: &PriceDocument, reference: &BTreeMap<DateTime<Utc>, f64>) -> Option<f64> {
+    let spreads: Vec<f64> = doc
+        .prices
+        .iter()
+        .filter_map(|point| Some(price_to_f64(point.price) - reference.get(&point.timestamp)?))
+        .collect();
+    if spreads.is_empty() {
+        return None;
+    }
+    let count = f64::from(u32::try_from(spreads.len()).ok()?);
+    Some(spreads.iter().sum::<f64>() / count)
+}
+
+/// Each point's price minus the baseline for its local hour in `tz`, in EUR/MWh.
+///
+/// `baseline_by_hour[h]` is the reference price for local hour `h`, e.g. a historical average.
+/// Sub-hourly points use the baseline of the hour they start in.
+#[must_use]
+pub fn delta_from_baseline(
+    current: &PriceDocument,
+    baseline_by_hour: &[f64; 24],
+    tz: Tz,
+) -> Vec<(DateTime<Utc>, f64)> {
+    current
+        .prices
+        .iter()
+        .map(|point| {
+            let hour = point.timestamp.with_timezone(&tz).hour() as usize;
+            (
+                point.timestamp,
+                price_to_f64(point.price) - baseline_by_hour[hour],
+            )
+        })
+        .collect()
+}
+
+/// Number of discrete state-of-charge levels used by [`optimal_dispatch`].
+const SOC_STEPS: u32 = 100;
+
+/// Battery schedule that maximizes arbitrage revenue over the document's periods.
+///
+/// Returns the power in kW for each period of the document's finest resolution, positive while
+/// charging and negative while discharging; positions left out of A03 curves get their own entry
+/// at the repeated price. The state of charge starts empty, stays within `capacity_kwh`, and
+/// changes by at most `power_kw` per hour. `efficiency` is the round-trip
+/// efficiency, applied to energy sold back when discharging. The state of charge is discretized
+/// into [`SOC_STEPS`] levels, so the schedule is optimal up to that granularity.
+///
+/// Returns an all-zero schedule if the capacity or power is not positive.
+#[must_use]
+pub fn optimal_dispatch(
+    doc: &PriceDocument,
+    capacity_kwh: f64,
+    power_kw: f64,
+    efficiency: f64,
+) -> Vec<(DateTime<Utc>, f64)> {
+    let periods = doc.filled_periods();
+    let hours = f64::from(i32::try_from(doc.finest_resolution().minutes()).unwrap_or(60)) / 60.0;
+    if capacity_kwh <= 0.0 || power_kw <= 0.0 {
+        return periods.iter().map(|&(t, _)| (t, 0.0)).collect();
+    }
+
+    let step_kwh = capacity_kwh / f64::from(SOC_STEPS);
+    let max_move = (0..=SOC_STEPS)
+        .take_while(|&levels| f64::from(levels) * step_kwh <= power_kw * hours + 1e-9)
+        .last()
+        .unwrap_or(0);
+    let levels = SOC_STEPS as usize + 1;
+
+    // value[i] is the best revenue from the current period onwards starting at level i
+    let mut value = vec![0.0; levels];
+    let mut choices = vec![vec![0_u32; levels]; periods.len()];
+    for (t, &(_, price)) in periods.iter().enumerate().rev() {
+        let price = price_to_f64(price);
+        let mut next = vec![0.0; levels];
+        for from in 0..=SOC_STEPS {
+            let low = from.saturating_sub(max_move);
+            let high = (from + max_move).min(SOC_STEPS);
+            let mut best = (value[from as usize], from);
+            for to in low..=high {
+                let delta_kwh = (f64::from(to) - f64::from(from)) * step_kwh;
+                let revenue = if delta_kwh > 0.0 {
+                    -price * delta_kwh / 1000.0
+                } else {
+                    -price * delta_kwh * efficiency / 1000.0
+                };
+                let total = revenue + value[to as usize];
+                if total > best.0 + 1e-12 {
+                    best = (total, to);
+                }
+            }
+            next[from as usize] = best.0;
+            choices[t][from as usize] = best.1;
+        }
+        value = next;
+    }
+
+    let mut level = 0_u32;
+    periods
+        .iter()
+        .zip(&choices)
+        .map(|(&(timestamp, _), choice)| {
+            let to = choice[level as usize];
+            let power = (f64::from(to) - f64::from(level)) * step_kwh / hours;
+            level = to;
+            (timestamp, power)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::price::{hourly_test_document, price_from_f64};
+    use chrono::{Duration, TimeZone};
+
+    fn assert_close(actual: (f64, f64), expected: (f64, f64)) {
+        assert!(
+            (actual.0 - expected.0).abs() < 1e-9 && (actual.1 - expected.1).abs() < 1e-9,
+            "{actual:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn test_cost_distribution() {
+        let doc = hourly_test_document(&[300.0, 100.0, 200.0, 400.0]);
+        let consumption: BTreeMap<_, _> = doc.prices.iter().map(|p| (p.timestamp, 1.0)).collect();
+
+        let curve = cost_distribution(&doc, &consumption);
+
+        // Total cost 1.0 EUR; cheapest half of the consumption costs 0.1 + 0.2
+        assert_eq!(curve.len(), 5);
+        assert_close(curve[0], (0.0, 0.0));
+        assert_close(curve[2], (0.5, 0.3));
+        assert_close(curve[4], (1.0, 1.0));
+    }
+
+    #[test]
+    fn test_cost_distribution_without_overlap() {
+        let doc = hourly_test_document(&[100.0, 200.0]);
+        let consumption = BTreeMap::from([(doc.period_end, 1.0)]);
+        assert!(cost_distribution(&doc, &consumption).is_empty());
+    }
+
+    #[test]
+    fn test_current_prices() {
+        let fi = hourly_test_document(&[10.0, 20.0]);
+        let mut se3 = hourly_test_document(&[30.0, 40.0]);
+        for point in &mut se3.prices {
+            point.timestamp += Duration::days(1);
+        }
+
+        let docs = HashMap::from([(BiddingZone::SE3, se3), (BiddingZone::FI, fi)]);
+        let now = Utc.with_ymd_and_hms(2024, 1, 15, 1, 30, 0).unwrap();
+
+        assert_eq!(
+            current_prices(&docs, now),
+            vec![
+                (BiddingZone::FI, Some(price_from_f64(20.0))),
+                (BiddingZone::SE3, None)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rank_zones_at() {
+        let fi = hourly_test_document(&[10.0, 60.0]);
+        let se3 = hourly_test_document(&[30.0, 15.0]);
+        let mut no2 = hourly_test_document(&[5.0, 5.0]);
+        for point in &mut no2.prices {
+            point.timestamp += Duration::days(1);
+        }
+        let de = hourly_test_document(&[90.0, 45.0]);
+
+        let docs = HashMap::from([
+            (BiddingZone::FI, fi),
+            (BiddingZone::SE3, se3),
+            (BiddingZone::NO2, no2),
+            (BiddingZone::DE, de),
+        ]);
+        let at = Utc.with_ymd_and_hms(2024, 1, 15, 1, 30, 0).unwrap();
+
+        assert_eq!(
+            rank_zones_at(&docs, at),
+            vec![
+                (BiddingZone::SE3, 15.0),
+                (BiddingZone::DE, 45.0),
+                (BiddingZone::FI, 60.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_delta_from_baseline() {
+        let doc = hourly_test_document(&[50.0, 45.0, 100.0]);
+        let mut baseline = [0.0; 24];
+        for (hour, price) in baseline.iter_mut().enumerate() {
+            *price = f64::from(u32::try_from(hour).unwrap()) * 10.0;
+        }
+        let start = doc.prices[0].timestamp;
+
+        // Helsinki is UTC+2 in January, so the points fall in local hours 2, 3 and 4
+        let deltas = delta_from_baseline(&doc, &baseline, chrono_tz::Europe::Helsinki);
+        assert_eq!(
+            deltas,
+            vec![
+                (start, 30.0),
+                (start + Duration::hours(1), 15.0),
+                (start + Duration::hours(2), 60.0),
+            ]
+        );
+
+        let utc: Vec<f64> = delta_from_baseline(&doc, &baseline, chrono_tz::UTC)
+            .into_iter()
+            .map(|(_, delta)| delta)
+            .collect();
+        assert_eq!(utc, vec![50.0, 35.0, 80.0]);
+    }
+
+    fn decimals(values: &[i64]) -> Vec<Decimal> {
+        values.iter().map(|&v| Decimal::from(v)).collect()
+    }
+
+    #[test]
+    fn test_rank_windows_overlapping() {
+        let prices = decimals(&[5, 1, 2, 9, 0, 3, 8]);
+
+        // Window averages of two: 3, 1.5, 5.5, 4.5, 1.5, 5.5
+        let ranked = rank_windows(&prices, 2, 4, false, WindowOrder::Cheapest);
+        assert_eq!(
+            ranked,
+            vec![
+                (1, Decimal::new(15, 1)),
+                (4, Decimal::new(15, 1)),
+                (0, Decimal::from(3)),
+                (3, Decimal::new(45, 1)),
+            ]
+        );
+
+        // Asking for more than exist returns every window
+        assert_eq!(
+            rank_windows(&prices, 2, 100, false, WindowOrder::Cheapest).len(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_rank_windows_non_overlapping() {
+        let prices = decimals(&[4, 1, 1, 4, 9, 9, 2, 2]);
+
+        // Overlapping selection reuses the cheap pair in its third pick
+        let overlapping = rank_windows(&prices, 2, 3, false, WindowOrder::Cheapest);
+        assert_eq!(
+            overlapping.iter().map(|w| w.0).collect::<Vec<_>>(),
+            vec![1, 6, 0]
+        );
+
+        let disjoint = rank_windows(&prices, 2, 3, true, WindowOrder::Cheapest);
+        assert_eq!(
+            disjoint,
+            vec![
+                (1, Decimal::ONE),
+                (6, Decimal::from(2)),
+                // 0 and 2 overlap the first pick and 5 overlaps the second
+                (3, Decimal::new(65, 1)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rank_windows_most_expensive() {
+        let prices = decimals(&[5, 1, 2, 9, 0, 3, 8]);
+
+        // Window averages of two: 3, 1.5, 5.5, 4.5, 1.5, 5.5
+        assert_eq!(
+            rank_windows(&prices, 2, 3, false, WindowOrder::MostExpensive),
+            vec![
+                (2, Decimal::new(55, 1)),
+                (5, Decimal::new(55, 1)),
+                (3, Decimal::new(45, 1)),
+            ]
+        );
+        assert_eq!(
+            rank_windows(&prices, 2, 3, true, WindowOrder::MostExpensive),
+            vec![
+                (2, Decimal::new(55, 1)),
+                (5, Decimal::new(55, 1)),
+                (0, Decimal::from(3)),
+            ]
+        );
+
+        // Plain f64 prices rank the same way
+        let floats = [5.0, 1.0, 2.0, 9.0];
+        assert_eq!(
+            rank_windows(&floats, 2, 1, false, WindowOrder::MostExpensive),
+            vec![(2, 5.5)]
+        );
+    }
+
+    #[test]
+    fn test_rank_windows_ties_and_edges() {
+        let prices = decimals(&[3, 3, 3, 3]);
+        assert_eq!(
+            rank_windows(&prices, 2, 3, false, WindowOrder::Cheapest),
+            vec![
+                (0, Decimal::from(3)),
+                (1, Decimal::from(3)),
+                (2, Decimal::from(3))
+            ]
+        );
+        // Of equal windows the earliest wins, and it blocks its neighbours
+        assert_eq!(
+            rank_windows(&prices, 2, 3, true, WindowOrder::Cheapest),
+            vec![(0, Decimal::from(3)), (2, Decimal::from(3))]
+        );
+
+        assert!(rank_windows(&prices, 0, 3, false, WindowOrder::Cheapest).is_empty());
+        assert!(rank_windows(&prices, 5, 3, false, WindowOrder::Cheapest).is_empty());
+        assert!(rank_windows(&prices, 2, 0, true, WindowOrder::Cheapest).is_empty());
+    }
+
+    #[test]
+    fn test_apply_consumer_price() {
+        let d = |s: &str| s.parse::<Decimal>().unwrap();
+
+        // (10 + 0.5) * 1.255 + 4 = 17.1775
+        assert_eq!(
+            apply_consumer_price(d("10"), d("25.5"), d("0.5"), d("4.0")),
+            d("17.1775")
+        );
+        // No adjustments leave the spot price untouched
+        assert_eq!(
+            apply_consumer_price(d("7.31"), Decimal::ZERO, Decimal::ZERO, Decimal::ZERO),
+            d("7.31")
+        );
+        // Negative spot prices carry negative VAT
+        assert_eq!(
+            apply_consumer_price(d("-2"), d("24"), Decimal::ZERO, d("3")),
+            d("0.52")
+        );
+    }
+
+    #[test]
+    fn test_optimal_dispatch_two_price_levels() {
+        let doc = hourly_test_document(&[20.0, 20.0, 20.0, 150.0, 150.0, 150.0]);
+
+        let schedule = optimal_dispatch(&doc, 10.0, 4.0, 0.9);
+        let powers: Vec<f64> = schedule.iter().map(|(_, kw)| *kw).collect();
+
+        assert_eq!(schedule.len(), 6);
+        assert!(powers.iter().all(|kw| kw.abs() <= 4.0 + 1e-9));
+        assert!(powers[..3].iter().all(|&kw| kw >= 0.0));
+        assert!(powers[3..].iter().all(|&kw| kw <= 0.0));
+
+        let mut soc = 0.0;
+        for kw in &powers {
+            soc += kw;
+            assert!((-1e-9..=10.0 + 1e-9).contains(&soc));
+        }
+        let charged: f64 = powers[..3].iter().sum();
+        let discharged: f64 = powers[3..].iter().sum();
+        assert!((charged - 10.0).abs() < 1e-9);
+        assert!((discharged + 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_optimal_dispatch_compressed_curve() {
+        let xml = include_bytes!("../tests/fixtures/day_ahead_prices_fi.xml");
+        let doc = crate::parser::parse_day_ahead_prices(xml).unwrap();
+
+        let schedule = optimal_dispatch(&doc, 10.0, 4.0, 0.9);
+
+        // 162 points, one entry per quarter-hour including the left-out positions
+        assert_eq!(schedule.len(), 192);
+        assert_eq!(schedule[0].0, doc.period_start);
+        assert_eq!(schedule[191].0, doc.period_end - Duration::minutes(15));
+        assert!(schedule.iter().all(|(_, kw)| kw.abs() <= 4.0 + 1e-9));
+    }
+
+    #[test]
+    fn test_optimal_dispatch_idle_when_unprofitable() {
+        // A 20% spread does not cover a 70% round-trip efficiency
+        let doc = hourly_test_document(&[100.0, 120.0]);
+        let schedule = optimal_dispatch(&doc, 10.0, 5.0, 0.7);
+        assert!(schedule.iter().all(|(_, kw)| *kw == 0.0));
+    }
+
+    #[test]
+    fn test_basis() {
+        let doc = hourly_test_document(&[50.0, 80.0, 20.0, 100.0]);
+        // The last hour has no reference and the extra reference point matches no price
+        let reference: BTreeMap<_, _> = [
+            (doc.prices[0].timestamp, 45.0),
+            (doc.prices[1].timestamp, 90.0),
+            (doc.prices[2].timestamp, 11.0),
+            (doc.period_end, 500.0),
+        ]
+        .into_iter()
+        .collect();
+
+        // (5 - 10 + 9) / 3
+        let mean = basis(&doc, &reference).unwrap();
+        assert!((mean - 4.0 / 3.0).abs() < 1e-9);
+
+        assert_eq!(basis(&doc, &BTreeMap::new()), None);
+    }
+}
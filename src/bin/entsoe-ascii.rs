@@ -1,11 +1,74 @@
 use chrono::{DateTime, Duration, LocalResult, TimeZone, Timelike, Utc};
 
 use chrono_tz::Tz;
+use clap::{Parser, ValueEnum};
+use entsoe::analysis::{WindowOrder, apply_consumer_price, rank_windows};
 use rusqlite::{Connection, Result as SqliteResult};
 use rust_decimal::prelude::*;
-use std::env;
+use serde::Serialize;
 use std::str::FromStr;
 
+/// Displays prices from a SQLite database as ASCII graphs and analysis tables.
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    after_help = "Examples:
+  entsoe-ascii prices.db --zone FI
+  entsoe-ascii prices.db --zone FI --timezone Europe/Helsinki --hours 48
+  entsoe-ascii prices.db --zone NO2 --future"
+)]
+struct Args {
+    /// Path to SQLite database file
+    database_path: String,
+
+    /// Bidding zone (e.g., FI, NO2, SE3)
+    #[arg(long)]
+    zone: String,
+
+    /// Display timezone, e.g. UTC, Europe/Helsinki, Europe/Stockholm
+    #[arg(long, value_parser = parse_timezone, default_value = "UTC")]
+    timezone: Tz,
+
+    /// Hours to display from now
+    #[arg(long, default_value_t = 24)]
+    hours: i64,
+
+    /// Show only future prices (default: show all in range)
+    #[arg(long)]
+    future: bool,
+
+    /// Use a comma as the decimal separator (e.g. 4,25)
+    #[arg(long)]
+    decimal_comma: bool,
+
+    /// Table style
+    #[arg(long, value_enum, default_value_t = TableStyle::Unicode)]
+    output: TableStyle,
+
+    /// VAT in percent applied to spot price and margin, e.g. 25.5
+    #[arg(long, default_value = "0")]
+    vat: Decimal,
+
+    /// Retailer margin in the display unit (¢/kWh for EUR, øre/kWh for NOK), added before VAT
+    #[arg(long, default_value = "0")]
+    margin: Decimal,
+
+    /// Transfer (grid) fee in the display unit, added after VAT
+    #[arg(long, default_value = "0")]
+    transfer: Decimal,
+
+    /// Print the window analysis and price series as JSON instead of tables and graph
+    #[arg(long)]
+    json: bool,
+
+    /// Use this RFC 3339 instant as the current time, for reproducible output
+    #[arg(long, value_parser = parse_now, hide = true)]
+    now: Option<DateTime<Utc>>,
+}
+
+/// Window lengths in hours analysed for cheapest and priciest runs.
+const WINDOW_HOURS: [usize; 6] = [1, 2, 3, 5, 8, 13];
+
 #[derive(Debug, Clone, Copy)]
 struct Period {
     start: DateTime<Utc>,
@@ -13,9 +76,30 @@ struct Period {
 }
 
 struct DisplayData {
+    /// Currency stored with the prices, e.g. EUR or NOK.
+    currency: String,
+    /// Prices in minor units per kWh, see [`display_unit`].
     periods: Vec<Period>,
 }
 
+/// Label for prices stored in `currency` and the number of display units per major unit.
+///
+/// Prices are shown in the minor unit (¢, øre, öre, p) per kWh where the symbol is known, and
+/// in the major unit otherwise.
+fn display_unit(currency: &str) -> (String, Decimal) {
+    let minor = match currency {
+        "EUR" => Some("¢"),
+        "NOK" | "DKK" => Some("øre"),
+        "SEK" => Some("öre"),
+        "GBP" => Some("p"),
+        _ => None,
+    };
+    match minor {
+        Some(symbol) => (format!("{}/kWh", symbol), Decimal::ONE_HUNDRED),
+        None => (format!("{}/kWh", currency), Decimal::ONE),
+    }
+}
+
 fn load_prices_from_db(
     conn: &Connection,
     price_area: &str,
@@ -33,12 +117,13 @@ fn load_prices_from_db(
 
     let mut rows = stmt.query([price_area, &from_str, &to_str])?;
 
+    let mut currency = None;
     let mut periods = Vec::new();
 
     while let Some(row) = rows.next()? {
         let timestamp_str: String = row.get(0)?;
         let price_str: String = row.get(1)?;
-        let _currency: String = row.get(2)?;
+        let row_currency: String = row.get(2)?;
 
         // Parse timestamp
         let timestamp = DateTime::parse_from_rfc3339(&timestamp_str)
@@ -51,122 +136,155 @@ fn load_prices_from_db(
             })?
             .with_timezone(&Utc);
 
-        // Parse price (stored as EUR/kWh) and convert to cents/kWh
-        let price_kwh = f64::from_str(&price_str).map_err(|e| {
+        // Prices are stored per kWh in the major unit of their currency
+        let price_kwh = Decimal::from_str(&price_str).map_err(|e| {
             rusqlite::Error::FromSqlConversionFailure(1, rusqlite::types::Type::Text, Box::new(e))
         })?;
 
-        // Convert to cents/kWh for display (multiply by 100)
-        let price_cents = Decimal::from_f64(price_kwh * 100.0).ok_or_else(|| {
-            rusqlite::Error::FromSqlConversionFailure(
-                1,
-                rusqlite::types::Type::Text,
-                Box::new(std::io::Error::new(
-                    std::io::ErrorKind::InvalidData,
-                    "Invalid price",
-                )),
-            )
-        })?;
+        let currency = currency.get_or_insert(row_currency);
+        let (_, scale) = display_unit(currency);
 
         periods.push(Period {
             start: timestamp,
-            price: price_cents,
+            price: price_kwh * scale,
         });
     }
 
-    Ok(DisplayData { periods })
+    Ok(DisplayData {
+        currency: currency.unwrap_or_default(),
+        periods,
+    })
 }
 
-fn find_cheapest_consecutive_hours(periods: &[Period], n: usize) -> Option<(usize, Decimal)> {
-    if periods.is_empty() || n == 0 {
-        return None;
-    }
-
-    // Need at least n periods to form a complete n-hour block
-    if periods.len() < n {
-        return None;
-    }
-
-    let mut min_sum: Decimal = periods.iter().take(n).map(|period| period.price).sum();
-    let mut min_index = 0;
-    let mut current_sum = min_sum;
-
-    // Only search up to positions where we have n complete hours ahead
-    for i in n..periods.len() {
-        current_sum += periods[i].price - periods[i - n].price;
-
-        if current_sum < min_sum {
-            min_sum = current_sum;
-            min_index = i + 1 - n;
-        }
-    }
-
-    Some((min_index, min_sum))
+/// Time range to load around `now`: `hours` ahead, and either `hours` back or, for future-only
+/// output, just enough to cover the previous hour.
+fn load_range(now: DateTime<Utc>, hours: i64, future_only: bool) -> (DateTime<Utc>, DateTime<Utc>) {
+    let start_time = if future_only {
+        now - Duration::minutes(75)
+    } else {
+        now - Duration::hours(hours) - Duration::minutes(15)
+    };
+    (start_time, now + Duration::hours(hours))
 }
 
-fn find_expensivest_consecutive_hours(periods: &[Period], n: usize) -> Option<(usize, Decimal)> {
-    if periods.is_empty() || n == 0 {
-        return None;
-    }
-
-    // Need at least n periods to form a complete n-hour block
-    if periods.len() < n {
-        return None;
-    }
-
-    let mut max_sum: Decimal = periods.iter().take(n).map(|period| period.price).sum();
-    let mut max_index = 0;
-    let mut current_sum = max_sum;
-
-    // Only search up to positions where we have n complete hours ahead
-    for i in n..periods.len() {
-        current_sum += periods[i].price - periods[i - n].price;
-
-        if current_sum > max_sum {
-            max_sum = current_sum;
-            max_index = i + 1 - n;
-        }
-    }
-
-    Some((max_index, max_sum))
+/// Keeps the periods from the start of the previous hour onwards.
+fn retain_future(periods: &mut Vec<Period>, now: DateTime<Utc>) {
+    // Round down to hour boundary to include the complete current hour
+    let cutoff = now - Duration::hours(1);
+    // Round down to the start of the hour in UTC
+    let cutoff_rounded = cutoff
+        .date_naive()
+        .and_hms_opt(cutoff.hour(), 0, 0)
+        .expect("hour() is always a valid hour of the day")
+        .and_utc();
+    periods.retain(|p| p.start >= cutoff_rounded);
 }
 
-fn render_cheapest(
+/// The cheapest or most expensive run of `n_periods` quarter-hours, as
+/// `(hours, start, end, average price)`.
+fn render_window(
     periods: &[Period],
     n_periods: usize,
+    order: WindowOrder,
 ) -> Option<(usize, DateTime<Utc>, DateTime<Utc>, Decimal)> {
-    let (index, total_price) = find_cheapest_consecutive_hours(periods, n_periods)?;
+    let prices: Vec<Decimal> = periods.iter().map(|period| period.price).collect();
+    let (index, avg_price) = *rank_windows(&prices, n_periods, 1, false, order).first()?;
 
     // n_periods is in 15-minute periods, convert to hours for display
     let n_hours = n_periods / 4;
 
-    // Divide by n_periods to get average, already in cents
-    let n_decimal = Decimal::from_usize(n_periods)?;
-    let avg_price = total_price / n_decimal;
-
     let time_start = periods[index].start;
     let time_end = periods[index].start + Duration::hours(n_hours as i64);
 
     Some((n_hours, time_start, time_end, avg_price))
 }
 
+fn render_cheapest(
+    periods: &[Period],
+    n_periods: usize,
+) -> Option<(usize, DateTime<Utc>, DateTime<Utc>, Decimal)> {
+    render_window(periods, n_periods, WindowOrder::Cheapest)
+}
+
 fn render_expensivest(
     periods: &[Period],
     n_periods: usize,
 ) -> Option<(usize, DateTime<Utc>, DateTime<Utc>, Decimal)> {
-    let (index, total_price) = find_expensivest_consecutive_hours(periods, n_periods)?;
+    render_window(periods, n_periods, WindowOrder::MostExpensive)
+}
 
-    // n_periods is in 15-minute periods, convert to hours for display
-    let n_hours = n_periods / 4;
+#[derive(Debug, Serialize)]
+struct JsonWindow {
+    hours: usize,
+    start_utc: String,
+    end_utc: String,
+    start_local: String,
+    end_local: String,
+    avg_cents_per_kwh: f64,
+}
 
-    // Divide by n_periods to get average, already in cents
-    let n_decimal = Decimal::from_usize(n_periods)?;
-    let avg_price = total_price / n_decimal;
+#[derive(Debug, Serialize)]
+struct JsonPrice {
+    start_utc: String,
+    start_local: String,
+    cents_per_kwh: f64,
+}
 
-    let time_start = periods[index].start;
-    let time_end = periods[index].start + Duration::hours(n_hours as i64);
+#[derive(Debug, Serialize)]
+struct JsonSummary {
+    currency: String,
+    /// Unit of the `*cents_per_kwh` fields, which hold minor units for any currency.
+    unit: String,
+    cheapest: Vec<JsonWindow>,
+    priciest: Vec<JsonWindow>,
+    prices: Vec<JsonPrice>,
+}
 
-    Some((n_hours, time_start, time_end, avg_price))
+impl JsonSummary {
+    fn new(data: &DisplayData, timezone: &Tz) -> Self {
+        let window =
+            |(hours, start, end, avg): (usize, DateTime<Utc>, DateTime<Utc>, Decimal)| JsonWindow {
+                hours,
+                start_utc: start.to_rfc3339(),
+                end_utc: end.to_rfc3339(),
+                start_local: start.with_timezone(timezone).to_rfc3339(),
+                end_local: end.with_timezone(timezone).to_rfc3339(),
+                avg_cents_per_kwh: avg.to_f64().unwrap_or(f64::NAN),
+            };
+
+        JsonSummary {
+            currency: data.currency.clone(),
+            unit: display_unit(&data.currency).0,
+            cheapest: WINDOW_HOURS
+                .iter()
+                .filter_map(|n| render_cheapest(&data.periods, n * 4))
+                .map(window)
+                .collect(),
+            priciest: WINDOW_HOURS
+                .iter()
+                .filter_map(|n| render_expensivest(&data.periods, n * 4))
+                .map(window)
+                .collect(),
+            prices: data
+                .periods
+                .iter()
+                .map(|p| JsonPrice {
+                    start_utc: p.start.to_rfc3339(),
+                    start_local: p.start.with_timezone(timezone).to_rfc3339(),
+                    cents_per_kwh: p.price.to_f64().unwrap_or(f64::NAN),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn format_price(price: Decimal, decimal_comma: bool) -> String {
+    let formatted = format!("{:.2}", price);
+    if decimal_comma {
+        formatted.replace('.', ",")
+    } else {
+        formatted
+    }
 }
 
 fn print_header(s: &str) {
@@ -183,14 +301,101 @@ fn print_info_header(price_area: &str, now: DateTime<Utc>, timezone: &Tz) {
     );
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum TableStyle {
+    /// Box-drawing characters
+    Unicode,
+    /// GitHub-flavoured Markdown
+    Markdown,
+    /// ASCII `+`, `-` and `|`
+    Plain,
+}
+
+/// Renders `rows` (the first being the header) as a table, padding every column to its widest cell.
+fn render_table(rows: &[Vec<String>], style: TableStyle, right_align: &[bool]) -> String {
+    let Some(header) = rows.first() else {
+        return String::new();
+    };
+
+    let mut widths = vec![0; header.len()];
+    for row in rows {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+
+    let format_row = |row: &[String], divider: &str| {
+        let cells = row
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| {
+                if right_align.get(i).copied().unwrap_or(false) {
+                    format!(" {:>width$} ", cell, width = widths[i])
+                } else {
+                    format!(" {:width$} ", cell, width = widths[i])
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(divider);
+        format!("{divider}{cells}{divider}")
+    };
+    let rule = |line: &str, left: &str, mid: &str, right: &str| {
+        let segments = widths
+            .iter()
+            .map(|&width| line.repeat(width + 2))
+            .collect::<Vec<_>>()
+            .join(mid);
+        format!("{left}{segments}{right}")
+    };
+
+    let mut lines = Vec::new();
+    match style {
+        TableStyle::Unicode => {
+            lines.push(rule("─", "┌", "┬", "┐"));
+            lines.push(format_row(header, "│"));
+            lines.push(rule("─", "├", "┼", "┤"));
+            lines.extend(rows[1..].iter().map(|row| format_row(row, "│")));
+            lines.push(rule("─", "└", "┴", "┘"));
+        }
+        TableStyle::Markdown => {
+            lines.push(format_row(header, "|"));
+            let separator = widths
+                .iter()
+                .enumerate()
+                .map(|(i, &width)| {
+                    if right_align.get(i).copied().unwrap_or(false) {
+                        format!(" {}: ", "-".repeat(width.saturating_sub(1)))
+                    } else {
+                        format!(" {} ", "-".repeat(width))
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("|");
+            lines.push(format!("|{separator}|"));
+            lines.extend(rows[1..].iter().map(|row| format_row(row, "|")));
+        }
+        TableStyle::Plain => {
+            lines.push(rule("-", "+", "+", "+"));
+            lines.push(format_row(header, "|"));
+            lines.push(rule("-", "+", "+", "+"));
+            lines.extend(rows[1..].iter().map(|row| format_row(row, "|")));
+            lines.push(rule("-", "+", "+", "+"));
+        }
+    }
+    lines.join("\n")
+}
+
 fn print_price_md_table(
     prices: Vec<(usize, DateTime<Utc>, DateTime<Utc>, Decimal)>,
+    unit: &str,
     timezone: &Tz,
+    decimal_comma: bool,
+    table_style: TableStyle,
 ) {
-    let headers = ["n", "start", "end", "avg(¢/kWh)"];
+    let average = format!("avg({})", unit);
+    let headers = ["n", "start", "end", average.as_str()];
     let time_format = "%a %H:%M";
 
-    let mut max_widths = headers.iter().map(|h| h.len()).collect::<Vec<_>>();
     let mut table_data = vec![
         headers
             .iter()
@@ -199,68 +404,21 @@ fn print_price_md_table(
     ];
 
     for (n, start, end, price) in prices {
-        let row = vec![
+        table_data.push(vec![
             n.to_string(),
             start
                 .with_timezone(timezone)
                 .format(time_format)
                 .to_string(),
             end.with_timezone(timezone).format(time_format).to_string(),
-            format!("{:.2}", price),
-        ];
-
-        for (i, cell) in row.iter().enumerate() {
-            max_widths[i] = std::cmp::max(max_widths[i], cell.len());
-        }
-
-        table_data.push(row);
-    }
-
-    // Print top border
-    println!(
-        "┌{}┐",
-        max_widths
-            .iter()
-            .map(|&width| "─".repeat(width + 2))
-            .collect::<Vec<_>>()
-            .join("┬")
-    );
-
-    for (i, row) in table_data.iter().enumerate() {
-        // Print row with vertical dividers
-        let row_str = row
-            .iter()
-            .enumerate()
-            .map(|(j, cell)| format!(" {:width$} ", cell, width = max_widths[j]))
-            .collect::<Vec<_>>()
-            .join("│");
-        println!("│{}│", row_str);
-
-        // Print header separator after the first row
-        if i == 0 {
-            println!(
-                "├{}┤",
-                max_widths
-                    .iter()
-                    .map(|&width| "─".repeat(width + 2))
-                    .collect::<Vec<_>>()
-                    .join("┼")
-            );
-        }
+            format_price(price, decimal_comma),
+        ]);
     }
 
-    // Print bottom border
-    println!(
-        "└{}┘",
-        max_widths
-            .iter()
-            .map(|&width| "─".repeat(width + 2))
-            .collect::<Vec<_>>()
-            .join("┴")
-    );
+    println!("{}", render_table(&table_data, table_style, &[]));
 }
 
-fn print_graph(periods: &[Period], timezone: &Tz) {
+fn print_graph(periods: &[Period], unit: &str, timezone: &Tz, decimal_comma: bool) {
     if periods.is_empty() {
         eprintln!("No data to graph");
         return;
@@ -279,20 +437,29 @@ fn print_graph(periods: &[Period], timezone: &Tz) {
         .map(|p| p.price.to_f64().unwrap_or(0.0))
         .collect();
 
-    println!(
-        "{}",
-        rasciigraph::plot(
-            converted_periods,
-            rasciigraph::Config::default()
-                .with_offset(0)
-                .with_height(7)
-                .with_width(38)
-                .with_caption(format!("¢/kWh ({} - {})", start_time_str, end_time_str))
-        )
+    let graph = rasciigraph::plot(
+        converted_periods,
+        rasciigraph::Config::default()
+            .with_offset(0)
+            .with_height(7)
+            .with_width(38)
+            .with_caption(format!("{} ({} - {})", unit, start_time_str, end_time_str)),
     );
+
+    // The graph only contains axis labels, box drawing and the caption, so every dot is a decimal point
+    if decimal_comma {
+        println!("{}", graph.replace('.', ","));
+    } else {
+        println!("{}", graph);
+    }
 }
 
-fn print_price_table(periods: &[Period], timezone: &Tz) {
+fn print_price_table(
+    periods: &[Period],
+    timezone: &Tz,
+    decimal_comma: bool,
+    table_style: TableStyle,
+) {
     if periods.is_empty() {
         return;
     }
@@ -300,51 +467,42 @@ fn print_price_table(periods: &[Period], timezone: &Tz) {
     println!();
     print_header("All prices");
 
-    // Print table header
-    println!("Time       :00   :15   :30   :45");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    let table_data = hour_rows(periods, timezone, decimal_comma);
+
+    println!(
+        "{}",
+        render_table(&table_data, table_style, &[false, true, true, true, true])
+    );
+}
+
+/// Groups periods into one row per local hour, with a column per quarter.
+///
+/// Hours are keyed by their UTC start, so the repeated hour on a fall-back day gets its own row
+/// and a skipped spring-forward hour simply has no row.
+fn hour_rows(periods: &[Period], timezone: &Tz, decimal_comma: bool) -> Vec<Vec<String>> {
+    let mut table_data = vec![
+        ["Time", ":00", ":15", ":30", ":45"]
+            .iter()
+            .map(|&h| h.to_string())
+            .collect::<Vec<String>>(),
+    ];
 
-    // Group periods by hour
     let mut current_hour: Option<DateTime<Utc>> = None;
     let mut hour_prices: Vec<Option<Decimal>> = vec![None; 4]; // 4 quarters per hour
 
     for period in periods {
         let local_time = period.start.with_timezone(timezone);
 
-        let hour = local_time.hour();
-        let minute = local_time.minute();
-
-        let naive_date = local_time.date_naive();
-        let hour_start_naive = naive_date.and_hms_opt(hour, 0, 0).unwrap();
-
-        // Robust conversion that handles DST ambiguity
-        let hour_start_local = match timezone.from_local_datetime(&hour_start_naive) {
-            LocalResult::Single(t) => t,
-            LocalResult::Ambiguous(earliest, latest) => {
-                // If ambiguous, pick the one that matches the original timestamp's offset
-                if earliest.offset() == local_time.offset() {
-                    earliest
-                } else {
-                    latest
-                }
-            }
-            LocalResult::None => {
-                // This happens if the hour was skipped (Spring forward).
-                // Rare if starting from a valid time, but good to handle.
-                panic!("Invalid local time (hour skipped by DST)");
-            }
-        };
-
-        let hour_start = hour_start_local.with_timezone(&Utc);
-
-        // Calculate which quarter of the hour (0, 1, 2, 3)
-        let quarter = (minute / 15) as usize;
+        // Truncate in absolute time so that both instances of a repeated hour stay apart
+        let hour_start = period.start
+            - Duration::minutes(i64::from(local_time.minute()))
+            - Duration::seconds(i64::from(local_time.second()));
+        let quarter = (local_time.minute() / 15) as usize;
 
-        // If we've moved to a new hour, print the previous hour's data
         if let Some(prev_hour) = current_hour
             && hour_start != prev_hour
         {
-            print_hour_row(&prev_hour, &hour_prices, timezone);
+            table_data.push(hour_row(&prev_hour, &hour_prices, timezone, decimal_comma));
             hour_prices = vec![None; 4];
         }
 
@@ -354,25 +512,33 @@ fn print_price_table(periods: &[Period], timezone: &Tz) {
         }
     }
 
-    // Print the last hour
     if let Some(hour) = current_hour {
-        print_hour_row(&hour, &hour_prices, timezone);
+        table_data.push(hour_row(&hour, &hour_prices, timezone, decimal_comma));
     }
+    table_data
 }
 
-fn print_hour_row(hour: &DateTime<Utc>, prices: &[Option<Decimal>], timezone: &Tz) {
+fn hour_row(
+    hour: &DateTime<Utc>,
+    prices: &[Option<Decimal>],
+    timezone: &Tz,
+    decimal_comma: bool,
+) -> Vec<String> {
     let local_time = hour.with_timezone(timezone);
-    let time_str = local_time.format("%a %H:%M").to_string();
-
-    print!("{:<10}", time_str);
+    // Label repeated fall-back hours with their offset so they can be told apart
+    let label = match timezone.from_local_datetime(&local_time.naive_local()) {
+        LocalResult::Ambiguous(..) => local_time.format("%a %H:%M %:z"),
+        _ => local_time.format("%a %H:%M"),
+    };
+    let mut row = vec![label.to_string()];
 
     for price_opt in prices {
-        match price_opt {
-            Some(price) => print!(" {:>5.2}", price),
-            None => print!("     -"),
-        }
+        row.push(match price_opt {
+            Some(price) => format_price(*price, decimal_comma),
+            None => "-".to_string(),
+        });
     }
-    println!();
+    row
 }
 
 fn parse_timezone(tz_str: &str) -> Result<Tz, String> {
@@ -384,81 +550,27 @@ fn parse_timezone(tz_str: &str) -> Result<Tz, String> {
     })
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() < 3 {
-        eprintln!("Usage: {} <DATABASE_PATH> <PRICE_AREA> [OPTIONS]", args[0]);
-        eprintln!();
-        eprintln!("Arguments:");
-        eprintln!("  DATABASE_PATH       Path to SQLite database file");
-        eprintln!("  PRICE_AREA          Bidding zone (e.g., FI, NO2, SE3)");
-        eprintln!();
-        eprintln!("Options:");
-        eprintln!("  --timezone TZ       Display timezone (default: UTC)");
-        eprintln!("                      Examples: UTC, Europe/Helsinki, Europe/Stockholm");
-        eprintln!("  --hours N           Hours to display from now (default: 24)");
-        eprintln!("  --future            Show only future prices (default: show all in range)");
-        eprintln!();
-        eprintln!("Examples:");
-        eprintln!("  {} prices.db FI", args[0]);
-        eprintln!(
-            "  {} prices.db FI --timezone Europe/Helsinki --hours 48",
-            args[0]
-        );
-        eprintln!("  {} prices.db NO2 --future", args[0]);
-        std::process::exit(1);
-    }
-
-    let db_path = &args[1];
-    let price_area = &args[2];
+fn parse_now(s: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(s)
+        .map(|dt| dt.with_timezone(&Utc))
+        .map_err(|e| format!("Invalid time '{}': {}", s, e))
+}
 
-    // Parse optional arguments
-    let mut timezone: Tz = Tz::UTC;
-    let mut hours: i64 = 24;
-    let mut future_only = false;
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
 
-    let mut i = 3;
-    while i < args.len() {
-        match args[i].as_str() {
-            "--timezone" => {
-                if i + 1 < args.len() {
-                    timezone = parse_timezone(&args[i + 1])?;
-                    i += 2;
-                } else {
-                    return Err("--timezone requires an argument".into());
-                }
-            }
-            "--hours" => {
-                if i + 1 < args.len() {
-                    hours = args[i + 1]
-                        .parse()
-                        .map_err(|_| format!("Invalid hours value: '{}'", args[i + 1]))?;
-                    i += 2;
-                } else {
-                    return Err("--hours requires an argument".into());
-                }
-            }
-            "--future" => {
-                future_only = true;
-                i += 1;
-            }
-            _ => {
-                return Err(format!("Unknown option: '{}'", args[i]).into());
-            }
-        }
-    }
+    let db_path = &args.database_path;
+    let price_area = &args.zone;
+    let timezone = args.timezone;
+    let hours = args.hours;
+    let future_only = args.future;
+    let decimal_comma = args.decimal_comma;
 
     eprintln!("Reading from database: {}", db_path);
     let conn = Connection::open(db_path)?;
 
-    let now = Utc::now();
-    let start_time = if future_only {
-        now - Duration::minutes(75)
-    } else {
-        now - Duration::hours(hours) - Duration::minutes(15)
-    };
-    let end_time = now + Duration::hours(hours);
+    let now = args.now.unwrap_or_else(Utc::now);
+    let (start_time, end_time) = load_range(now, hours, future_only);
 
     eprintln!(
         "Loading prices for {} from {} to {}",
@@ -476,17 +588,8 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
 
     // Filter to future only if requested
-    // Round down to hour boundary to include the complete current hour
     if future_only {
-        let cutoff = now - Duration::hours(1);
-        // Round down to the start of the hour in UTC
-        let hour = cutoff.format("%H").to_string().parse::<u32>().unwrap_or(0);
-        let cutoff_rounded = cutoff
-            .date_naive()
-            .and_hms_opt(hour, 0, 0)
-            .unwrap()
-            .and_utc();
-        data.periods.retain(|p| p.start >= cutoff_rounded);
+        retain_future(&mut data.periods, now);
     }
 
     if data.periods.is_empty() {
@@ -496,43 +599,300 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     eprintln!("Loaded {} price points\n", data.periods.len());
 
+    // Consumer prices drive both the display and the window calculations
+    for period in &mut data.periods {
+        period.price = apply_consumer_price(period.price, args.vat, args.margin, args.transfer);
+    }
+
+    if args.json {
+        let summary = JsonSummary::new(&data, &timezone);
+        println!("{}", serde_json::to_string_pretty(&summary)?);
+        return Ok(());
+    }
+
+    let (unit, _) = display_unit(&data.currency);
+
     // Display output
     print_info_header(price_area, now, &timezone);
 
     // Cheapest consecutive hours
     print_header("Cheapest consecutive n hours & average price");
     let mut cheapest: Vec<(usize, DateTime<Utc>, DateTime<Utc>, Decimal)> = Vec::new();
-    for n in [1, 2, 3, 5, 8, 13] {
+    for n in WINDOW_HOURS {
         // Convert hours to 15-minute periods (4 periods per hour)
         if let Some(result) = render_cheapest(&data.periods, n * 4) {
             cheapest.push(result);
         }
     }
     if !cheapest.is_empty() {
-        print_price_md_table(cheapest, &timezone);
+        print_price_md_table(cheapest, &unit, &timezone, decimal_comma, args.output);
         println!();
     }
 
     // Most expensive consecutive hours
     print_header("Priciest consecutive n hours & average price");
     let mut expensivest: Vec<(usize, DateTime<Utc>, DateTime<Utc>, Decimal)> = Vec::new();
-    for n in [1, 2, 3, 5, 8, 13] {
+    for n in WINDOW_HOURS {
         // Convert hours to 15-minute periods (4 periods per hour)
         if let Some(result) = render_expensivest(&data.periods, n * 4) {
             expensivest.push(result);
         }
     }
     if !expensivest.is_empty() {
-        print_price_md_table(expensivest, &timezone);
+        print_price_md_table(expensivest, &unit, &timezone, decimal_comma, args.output);
         println!();
     }
 
     // Graph
     print_header("Spot graph");
-    print_graph(&data.periods, &timezone);
+    print_graph(&data.periods, &unit, &timezone, decimal_comma);
 
     // Price table
-    print_price_table(&data.periods, &timezone);
+    print_price_table(&data.periods, &timezone, decimal_comma, args.output);
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_table() -> Vec<Vec<String>> {
+        vec![
+            vec!["n".to_string(), "avg".to_string()],
+            vec!["1".to_string(), "4.25".to_string()],
+            vec!["13".to_string(), "10.50".to_string()],
+        ]
+    }
+
+    #[test]
+    fn test_hour_rows_fall_back_day() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_se3_fall_back.xml");
+        let doc = entsoe::parse_day_ahead_prices(xml).unwrap();
+        let periods: Vec<Period> = doc
+            .prices
+            .iter()
+            .map(|p| Period {
+                start: p.timestamp,
+                price: Decimal::from_str(&p.price.to_string()).unwrap(),
+            })
+            .collect();
+        let timezone: Tz = "Europe/Stockholm".parse().unwrap();
+
+        let rows = hour_rows(&periods, &timezone, false);
+
+        let labels: Vec<&str> = rows[1..].iter().map(|row| row[0].as_str()).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "Sun 01:00",
+                "Sun 02:00 +02:00",
+                "Sun 02:00 +01:00",
+                "Sun 03:00"
+            ]
+        );
+        let cells: Vec<&String> = rows[1..].iter().flat_map(|row| &row[1..]).collect();
+        assert_eq!(cells.len(), 16);
+        assert!(cells.iter().all(|cell| cell.as_str() != "-"));
+    }
+
+    #[test]
+    fn test_render_table_unicode() {
+        assert_eq!(
+            render_table(&sample_table(), TableStyle::Unicode, &[false, true]),
+            "┌────┬───────┐\n\
+             │ n  │   avg │\n\
+             ├────┼───────┤\n\
+             │ 1  │  4.25 │\n\
+             │ 13 │ 10.50 │\n\
+             └────┴───────┘"
+        );
+    }
+
+    #[test]
+    fn test_render_table_markdown() {
+        assert_eq!(
+            render_table(&sample_table(), TableStyle::Markdown, &[false, true]),
+            "| n  |   avg |\n\
+             | -- | ----: |\n\
+             | 1  |  4.25 |\n\
+             | 13 | 10.50 |"
+        );
+    }
+
+    #[test]
+    fn test_render_table_plain() {
+        assert_eq!(
+            render_table(&sample_table(), TableStyle::Plain, &[]),
+            "+----+-------+\n\
+             | n  | avg   |\n\
+             +----+-------+\n\
+             | 1  | 4.25  |\n\
+             | 13 | 10.50 |\n\
+             +----+-------+"
+        );
+    }
+
+    #[test]
+    fn test_json_summary() {
+        let start = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+        // One hour of quarter-hourly prices: 4 periods, cheapest 1 h window is the whole hour
+        let data = DisplayData {
+            currency: "EUR".to_string(),
+            periods: [1.0, 2.0, 3.0, 6.0]
+                .iter()
+                .enumerate()
+                .map(|(i, &price)| Period {
+                    start: start + Duration::minutes(15 * i64::try_from(i).unwrap()),
+                    price: Decimal::from_f64(price).unwrap(),
+                })
+                .collect(),
+        };
+
+        let value =
+            serde_json::to_value(JsonSummary::new(&data, &chrono_tz::Europe::Helsinki)).unwrap();
+
+        assert_eq!(value["currency"], "EUR");
+        assert_eq!(value["unit"], "¢/kWh");
+        assert_eq!(
+            value["cheapest"],
+            serde_json::json!([{
+                "hours": 1,
+                "start_utc": "2025-10-12T22:00:00+00:00",
+                "end_utc": "2025-10-12T23:00:00+00:00",
+                "start_local": "2025-10-13T01:00:00+03:00",
+                "end_local": "2025-10-13T02:00:00+03:00",
+                "avg_cents_per_kwh": 3.0
+            }])
+        );
+        assert_eq!(value["priciest"][0]["avg_cents_per_kwh"], 3.0);
+        assert_eq!(value["prices"].as_array().unwrap().len(), 4);
+        assert_eq!(
+            value["prices"][3],
+            serde_json::json!({
+                "start_utc": "2025-10-12T22:45:00+00:00",
+                "start_local": "2025-10-13T01:45:00+03:00",
+                "cents_per_kwh": 6.0
+            })
+        );
+    }
+
+    #[test]
+    fn test_load_prices_honors_stored_currency() {
+        let conn = Connection::open_in_memory().unwrap();
+        entsoe::storage::init_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO prices VALUES ('2025-10-12T22:00:00+00:00', '0.41250', 'NOK', 'NO2');
+             INSERT INTO prices VALUES ('2025-10-12T22:15:00+00:00', '0.39800', 'NOK', 'NO2');",
+        )
+        .unwrap();
+        let from = Utc.with_ymd_and_hms(2025, 10, 12, 0, 0, 0).unwrap();
+        let to = from + Duration::days(1);
+
+        let data = load_prices_from_db(&conn, "NO2", from, to).unwrap();
+
+        assert_eq!(data.currency, "NOK");
+        assert_eq!(display_unit(&data.currency).0, "øre/kWh");
+        assert_eq!(data.periods.len(), 2);
+        assert_eq!(data.periods[0].price, Decimal::new(4125, 2));
+    }
+
+    #[test]
+    fn test_display_unit() {
+        assert_eq!(
+            display_unit("EUR"),
+            ("¢/kWh".to_string(), Decimal::ONE_HUNDRED)
+        );
+        assert_eq!(display_unit("SEK").0, "öre/kWh");
+        assert_eq!(display_unit("PLN"), ("PLN/kWh".to_string(), Decimal::ONE));
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Args::try_parse_from([
+            "entsoe-ascii",
+            "prices.db",
+            "--zone",
+            "FI",
+            "--timezone",
+            "Europe/Helsinki",
+            "--future",
+        ])
+        .unwrap();
+        assert_eq!(args.zone, "FI");
+        assert_eq!(args.timezone, chrono_tz::Europe::Helsinki);
+        assert_eq!(args.hours, 24);
+        assert!(args.future);
+
+        let err = Args::try_parse_from([
+            "entsoe-ascii",
+            "prices.db",
+            "--zone",
+            "FI",
+            "--hours",
+            "abc",
+        ])
+        .unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+        assert!(
+            Args::try_parse_from([
+                "entsoe-ascii",
+                "prices.db",
+                "--zone",
+                "FI",
+                "--timezone",
+                "Mars/Base"
+            ])
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn test_now_override_future_cutoff() {
+        let args = Args::try_parse_from([
+            "entsoe-ascii",
+            "prices.db",
+            "--zone",
+            "FI",
+            "--future",
+            "--now",
+            "2025-10-13T03:20:00+02:00",
+        ])
+        .unwrap();
+        let now = args.now.unwrap();
+        assert_eq!(now, Utc.with_ymd_and_hms(2025, 10, 13, 1, 20, 0).unwrap());
+
+        let (start, end) = load_range(now, args.hours, args.future);
+        assert_eq!(start, Utc.with_ymd_and_hms(2025, 10, 13, 0, 5, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2025, 10, 14, 1, 20, 0).unwrap());
+
+        let first = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+        let mut periods: Vec<Period> = (0..20)
+            .map(|i| Period {
+                start: first + Duration::minutes(15 * i),
+                price: Decimal::ONE,
+            })
+            .collect();
+        retain_future(&mut periods, now);
+
+        // Everything from the start of the previous hour (00:00 UTC) onwards
+        assert_eq!(periods.len(), 12);
+        assert_eq!(
+            periods[0].start,
+            Utc.with_ymd_and_hms(2025, 10, 13, 0, 0, 0).unwrap()
+        );
+
+        assert!(
+            Args::try_parse_from(["entsoe-ascii", "prices.db", "--zone", "FI", "--now", "soon"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_format_price() {
+        let price = Decimal::new(425, 2);
+        assert_eq!(format_price(price, false), "4.25");
+        assert_eq!(format_price(price, true), "4,25");
+        assert_eq!(format_price(Decimal::new(-1, 1), true), "-0,10");
+    }
+}
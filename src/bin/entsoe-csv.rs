@@ -1,80 +1,105 @@
+use clap::{Parser, ValueEnum};
+use entsoe::PriceDocument;
 use rusqlite::{Connection, Result as SqliteResult};
-use std::env;
+use serde::Serialize;
 use std::io::{self, Write};
 
-fn export_to_csv(conn: &Connection, price_area: Option<&str>) -> SqliteResult<()> {
-    let stdout = io::stdout();
-    let mut handle = stdout.lock();
+/// Exports prices from a SQLite database to stdout.
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    after_help = "Examples:
+  entsoe-csv prices.db                      # Export all prices
+  entsoe-csv prices.db --zone FI            # Export only FI prices
+  entsoe-csv prices.db > out.csv            # Save to file
+  entsoe-csv prices.db --zone FI --format json | jq .
+
+Output: records to stdout with fields: timestamp,price_per_kwh,currency,price_area"
+)]
+struct Args {
+    /// Path to SQLite database file
+    database_path: String,
+
+    /// Filter by price area (e.g., FI, NO2)
+    #[arg(long)]
+    zone: Option<String>,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Csv)]
+    format: OutputFormat,
+}
 
-    writeln!(handle, "timestamp,price_per_kwh,currency,price_area")
-        .expect("Failed to write header");
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Csv,
+    Json,
+    Ndjson,
+}
 
-    if let Some(area) = price_area {
-        let mut stmt = conn.prepare(
-            "SELECT timestamp, price, currency, price_area FROM prices WHERE price_area = ?1 ORDER BY timestamp"
-        )?;
-        let mut rows = stmt.query([area])?;
-
-        while let Some(row) = rows.next()? {
-            let timestamp: String = row.get(0)?;
-            let price: String = row.get(1)?;
-            let currency: String = row.get(2)?;
-            let price_area: String = row.get(3)?;
-
-            writeln!(
-                handle,
-                "{},{},{},{}",
-                timestamp, price, currency, price_area
-            )
-            .expect("Failed to write row");
+#[derive(Debug, Serialize)]
+struct PriceRow {
+    timestamp: String,
+    price_per_kwh: f64,
+    currency: String,
+    price_area: String,
+}
+
+fn query_rows(conn: &Connection, price_area: Option<&str>) -> SqliteResult<Vec<PriceRow>> {
+    let mut stmt = conn.prepare(
+        "SELECT timestamp, price, currency, price_area FROM prices
+         WHERE ?1 IS NULL OR price_area = ?1
+         ORDER BY timestamp, price_area",
+    )?;
+
+    stmt.query_map([price_area], |row| {
+        let price: String = row.get(1)?;
+        Ok(PriceRow {
+            timestamp: row.get(0)?,
+            // The schema only admits numeric text, so this cannot fail for stored rows
+            price_per_kwh: price.parse().unwrap_or(f64::NAN),
+            currency: row.get(2)?,
+            price_area: row.get(3)?,
+        })
+    })?
+    .collect()
+}
+
+fn write_rows(
+    out: &mut impl Write,
+    rows: &[PriceRow],
+    format: OutputFormat,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        OutputFormat::Csv => {
+            writeln!(out, "{}", PriceDocument::CSV_HEADER)?;
+            for row in rows {
+                writeln!(
+                    out,
+                    "{},{:.5},{},{}",
+                    row.timestamp, row.price_per_kwh, row.currency, row.price_area
+                )?;
+            }
+        }
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *out, rows)?;
+            writeln!(out)?;
         }
-    } else {
-        let mut stmt = conn.prepare(
-            "SELECT timestamp, price, currency, price_area FROM prices ORDER BY timestamp, price_area"
-        )?;
-        let mut rows = stmt.query([])?;
-
-        while let Some(row) = rows.next()? {
-            let timestamp: String = row.get(0)?;
-            let price: String = row.get(1)?;
-            let currency: String = row.get(2)?;
-            let price_area: String = row.get(3)?;
-
-            writeln!(
-                handle,
-                "{},{},{},{}",
-                timestamp, price, currency, price_area
-            )
-            .expect("Failed to write row");
+        OutputFormat::Ndjson => {
+            for row in rows {
+                serde_json::to_writer(&mut *out, row)?;
+                writeln!(out)?;
+            }
         }
     }
-
     Ok(())
 }
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() < 2 {
-        eprintln!("Usage: {} <DATABASE_PATH> [PRICE_AREA]", args[0]);
-        eprintln!();
-        eprintln!("Arguments:");
-        eprintln!("  DATABASE_PATH       Path to SQLite database file");
-        eprintln!("  PRICE_AREA          Optional: filter by price area (e.g., FI, NO2)");
-        eprintln!();
-        eprintln!("Examples:");
-        eprintln!("  {} prices.db           # Export all prices", args[0]);
-        eprintln!("  {} prices.db FI        # Export only FI prices", args[0]);
-        eprintln!("  {} prices.db > out.csv # Save to file", args[0]);
-        eprintln!();
-        eprintln!(
-            "Output: CSV to stdout with columns: timestamp,price_per_kwh,currency,price_area"
-        );
-        std::process::exit(1);
-    }
+    let args = Args::parse();
 
-    let db_path = &args[1];
-    let price_area = args.get(2).map(|s| s.as_str());
+    let db_path = &args.database_path;
+    let price_area = args.zone.as_deref();
+    let format = args.format;
 
     eprintln!("Reading from database: {}", db_path);
     let conn = Connection::open(db_path)?;
@@ -83,9 +108,82 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         eprintln!("Filtering by price area: {}", area);
     }
 
-    export_to_csv(&conn, price_area)?;
+    let rows = query_rows(&conn, price_area)?;
+    let stdout = io::stdout();
+    write_rows(&mut stdout.lock(), &rows, format)?;
 
     eprintln!("Export complete");
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_rows() -> Vec<PriceRow> {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE prices (timestamp TEXT, price TEXT, currency TEXT, price_area TEXT);
+             INSERT INTO prices VALUES ('2025-10-12T22:00:00+00:00', '0.00267', 'EUR', 'FI');
+             INSERT INTO prices VALUES ('2025-10-12T22:00:00+00:00', '0.04120', 'EUR', 'SE3');",
+        )
+        .unwrap();
+        query_rows(&conn, Some("FI")).unwrap()
+    }
+
+    fn render(format: OutputFormat) -> String {
+        let mut out = Vec::new();
+        write_rows(&mut out, &test_rows(), format).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_args() {
+        let args = Args::try_parse_from([
+            "entsoe-csv",
+            "prices.db",
+            "--zone",
+            "FI",
+            "--format",
+            "ndjson",
+        ])
+        .unwrap();
+        assert_eq!(args.zone.as_deref(), Some("FI"));
+        assert_eq!(args.format, OutputFormat::Ndjson);
+
+        assert!(Args::try_parse_from(["entsoe-csv", "prices.db", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn test_csv_output() {
+        assert_eq!(
+            render(OutputFormat::Csv),
+            "timestamp,price_per_kwh,currency,price_area\n\
+             2025-10-12T22:00:00+00:00,0.00267,EUR,FI\n"
+        );
+    }
+
+    #[test]
+    fn test_json_output() {
+        let value: serde_json::Value = serde_json::from_str(&render(OutputFormat::Json)).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!([{
+                "timestamp": "2025-10-12T22:00:00+00:00",
+                "price_per_kwh": 0.00267,
+                "currency": "EUR",
+                "price_area": "FI"
+            }])
+        );
+    }
+
+    #[test]
+    fn test_ndjson_output() {
+        let output = render(OutputFormat::Ndjson);
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 1);
+        let value: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(value["price_area"], "FI");
+    }
+}
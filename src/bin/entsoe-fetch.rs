@@ -1,123 +1,265 @@
-use chrono::{Duration, Utc};
-use entsoe::{BiddingZone, EntsoeClient};
-use rusqlite::{Connection, params};
+use chrono::{DateTime, Duration, Utc};
+use clap::Parser;
+use entsoe::client::read_api_token;
+use entsoe::storage::{PriceStore, init_schema};
+use entsoe::{BiddingZone, EntsoeClient, HttpTransport};
+use rusqlite::Connection;
+use serde::Serialize;
 use std::env;
+use std::path::PathBuf;
 
-fn init_database(conn: &Connection) -> Result<(), Box<dyn std::error::Error>> {
-    // Create prices table with CHECK constraints for data validation:
-    // - timestamp: RFC3339 format (YYYY-MM-DDTHH:MM:SS...)
-    // - price: Must be numeric (can be negative - electricity prices can go negative)
-    // - currency: Must be 3 uppercase letters (e.g., EUR, SEK)
-    // - price_area: Must be 2-8 characters (e.g., FI, NO2, IT-North)
-    conn.execute(
-        "CREATE TABLE IF NOT EXISTS prices (
-            timestamp TEXT NOT NULL CHECK(timestamp GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9]T[0-9][0-9]:[0-9][0-9]:[0-9][0-9]*'),
-            price TEXT NOT NULL CHECK(price GLOB '*[0-9]*' AND typeof(CAST(price AS REAL)) = 'real'),
-            currency TEXT NOT NULL CHECK(length(currency) = 3 AND currency = upper(currency)),
-            price_area TEXT NOT NULL CHECK(length(price_area) >= 2 AND length(price_area) <= 8),
-            PRIMARY KEY (timestamp, price_area)
-        )",
-        [],
-    )?;
-
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_timestamp ON prices(timestamp)",
-        [],
-    )?;
-
-    conn.execute(
-        "CREATE INDEX IF NOT EXISTS idx_price_area ON prices(price_area)",
-        [],
-    )?;
+/// Fetches day-ahead prices from the ENTSO-E API and stores them in SQLite.
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    after_help = "Environment variables:
+  ENTSOE_API_TOKEN    Your ENTSO-E API token, unless --api-token or --token-file is given
 
-    Ok(())
+Examples:
+  ENTSOE_API_TOKEN=your-token entsoe-fetch prices.db                     # Fetch all zones
+  ENTSOE_API_TOKEN=your-token entsoe-fetch prices.db --zone FI           # Fetch only FI
+  ENTSOE_API_TOKEN=your-token entsoe-fetch prices.db --zone FI --hours 48
+  entsoe-fetch prices.db --token-file ~/.config/entsoe/token              # Token from a file
+
+Note: Writes are idempotent - safe to run multiple times"
+)]
+struct Args {
+    /// Path to SQLite database file
+    database_path: String,
+
+    /// Older positional form of --zone or --hours: `FI`, `FI 48` or just `48`
+    #[arg(hide = true, value_name = "ZONE_OR_HOURS", value_parser = parse_zone_or_hours)]
+    positional_zone_or_hours: Option<ZoneOrHours>,
+
+    /// Older positional form of --hours, after a positional zone
+    #[arg(
+        hide = true,
+        value_name = "HOURS",
+        requires = "positional_zone_or_hours",
+        conflicts_with = "hours"
+    )]
+    positional_hours: Option<i64>,
+
+    /// Specific zone (e.g., FI, NO2); omit to fetch all zones
+    #[arg(long, value_parser = parse_zone)]
+    zone: Option<BiddingZone>,
+
+    /// Hours from now to fetch [default: 24]
+    #[arg(long)]
+    hours: Option<i64>,
+
+    /// Retry failed zones up to N more times at the end of the run
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    retry_failed: u32,
+
+    /// Also write a JSON summary of the run to stdout
+    #[arg(long)]
+    summary_json: bool,
+
+    /// API token; visible in process listings, so prefer --token-file
+    #[arg(long, value_name = "TOKEN")]
+    api_token: Option<String>,
+
+    /// Read the API token from this file (surrounding whitespace is trimmed)
+    #[arg(long, value_name = "PATH")]
+    token_file: Option<PathBuf>,
+}
+
+impl Args {
+    /// Combines `--zone` and `--hours` with the older positional form.
+    fn zone_and_hours(&self) -> Result<(Option<BiddingZone>, i64), String> {
+        let (zone_arg, hours_arg) = match self.positional_zone_or_hours {
+            Some(ZoneOrHours::Hours(hours)) if self.positional_hours.is_none() => {
+                (None, Some(hours))
+            }
+            Some(ZoneOrHours::Hours(_)) => {
+                return Err("Expected a zone before positional hours".into());
+            }
+            Some(ZoneOrHours::Zone(zone)) => (Some(zone), self.positional_hours),
+            None => (None, None),
+        };
+
+        let zone = match (self.zone, zone_arg) {
+            (Some(_), Some(_)) => return Err("Zone given both positionally and with --zone".into()),
+            (zone, None) | (None, zone) => zone,
+        };
+
+        let hours = match (self.hours, hours_arg) {
+            (Some(_), Some(_)) => {
+                return Err("Hours given both positionally and with --hours".into());
+            }
+            (hours, None) | (None, hours) => hours.unwrap_or(24),
+        };
+
+        Ok((zone, hours))
+    }
+
+    /// Resolves the API token: `--api-token`, then `--token-file`, then `env_token`.
+    fn api_token(&self, env_token: Option<String>) -> Result<String, String> {
+        if let Some(token) = &self.api_token {
+            return Ok(token.clone());
+        }
+        if let Some(path) = &self.token_file {
+            return read_api_token(path)
+                .map_err(|e| format!("Cannot read token file {}: {}", path.display(), e));
+        }
+        env_token.ok_or_else(|| {
+            "No API token: set ENTSOE_API_TOKEN or pass --token-file or --api-token".to_string()
+        })
+    }
+}
+
+fn parse_zone(code: &str) -> Result<BiddingZone, String> {
+    BiddingZone::from_code(code).ok_or_else(|| {
+        format!(
+            "Invalid bidding zone: '{}'. Valid zones: FI, NO2, SE3, DE, FR, etc.",
+            code
+        )
+    })
+}
+
+/// The first positional argument: hours for all zones when it's an integer, else a zone.
+#[derive(Debug, Clone, Copy)]
+enum ZoneOrHours {
+    Zone(BiddingZone),
+    Hours(i64),
+}
+
+fn parse_zone_or_hours(value: &str) -> Result<ZoneOrHours, String> {
+    match value.parse() {
+        Ok(hours) => Ok(ZoneOrHours::Hours(hours)),
+        Err(_) => parse_zone(value).map(ZoneOrHours::Zone),
+    }
 }
 
-fn store_prices(
-    conn: &Connection,
+/// Delay before each retry round, giving transient API failures time to clear.
+const RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Fetches one zone and stores it, returning the number of prices stored.
+async fn fetch_and_store<T: HttpTransport, S: PriceStore>(
+    client: &EntsoeClient<T>,
+    store: &S,
     zone: BiddingZone,
-    price_doc: &entsoe::PriceDocument,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let mut stmt = conn.prepare(
-        "INSERT OR REPLACE INTO prices (timestamp, price, currency, price_area)
-         VALUES (?1, ?2, ?3, ?4)",
-    )?;
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<usize, String> {
+    let price_doc = client
+        .get_day_ahead_prices(zone, start, end)
+        .await
+        .map_err(|e| e.to_string())?;
+    store
+        .store(zone, &price_doc)
+        .await
+        .map_err(|e| e.to_string())
+}
 
-    let zone_code = zone.code();
+/// Machine-readable summary of a run, printed with `--summary-json`.
+#[derive(Debug, Serialize)]
+struct RunSummary {
+    successful: usize,
+    total_prices: usize,
+    failed: Vec<FailedZone>,
+}
 
-    for price_point in &price_doc.prices {
-        let timestamp = price_point.timestamp.to_rfc3339();
-        let price = format!("{:.5}", price_point.price_per_kwh());
+#[derive(Debug, Serialize)]
+struct FailedZone {
+    zone: String,
+    error: String,
+}
 
-        stmt.execute(params![timestamp, price, &price_doc.currency, zone_code,])?;
+impl RunSummary {
+    fn new(successful: usize, total_prices: usize, failed: &[(BiddingZone, String)]) -> Self {
+        Self {
+            successful,
+            total_prices,
+            failed: failed
+                .iter()
+                .map(|(zone, error)| FailedZone {
+                    zone: zone.to_string(),
+                    error: error.clone(),
+                })
+                .collect(),
+        }
     }
+}
 
-    Ok(())
+#[derive(Debug, Default)]
+struct RetryOutcome {
+    recovered_zones: usize,
+    prices_stored: usize,
+    still_failed: Vec<(BiddingZone, String)>,
 }
 
-#[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
+/// Re-attempts failed zones up to `attempts` rounds, sleeping `delay` before each round.
+async fn retry_failed_zones<T: HttpTransport, S: PriceStore>(
+    client: &EntsoeClient<T>,
+    store: &S,
+    failed_zones: Vec<(BiddingZone, String)>,
+    attempts: u32,
+    delay: std::time::Duration,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> RetryOutcome {
+    let mut outcome = RetryOutcome {
+        still_failed: failed_zones,
+        ..RetryOutcome::default()
+    };
+
+    for attempt in 1..=attempts {
+        if outcome.still_failed.is_empty() {
+            break;
+        }
 
-    if args.len() < 2 {
-        eprintln!("Usage: {} <DATABASE_PATH> [BIDDING_ZONE] [HOURS]", args[0]);
-        eprintln!();
-        eprintln!("Environment variables:");
-        eprintln!("  ENTSOE_API_TOKEN    Required: Your ENTSO-E API token");
-        eprintln!();
-        eprintln!("Arguments:");
-        eprintln!("  DATABASE_PATH       Path to SQLite database file");
-        eprintln!(
-            "  BIDDING_ZONE        Optional: specific zone (e.g., FI, NO2) or omit for all zones"
-        );
-        eprintln!("  HOURS               Optional: hours from now (default: 24)");
         eprintln!();
-        eprintln!("Examples:");
-        eprintln!(
-            "  ENTSOE_API_TOKEN=your-token {} prices.db           # Fetch all zones",
-            args[0]
-        );
         eprintln!(
-            "  ENTSOE_API_TOKEN=your-token {} prices.db FI        # Fetch only FI",
-            args[0]
+            "Retrying {} failed zones (attempt {}/{})...",
+            outcome.still_failed.len(),
+            attempt,
+            attempts
         );
-        eprintln!(
-            "  ENTSOE_API_TOKEN=your-token {} prices.db FI 48     # Fetch FI, 48 hours",
-            args[0]
-        );
-        eprintln!();
-        eprintln!("Note: Writes are idempotent - safe to run multiple times");
-        std::process::exit(1);
+        tokio::time::sleep(delay).await;
+
+        let mut failed = Vec::new();
+        for (zone, _) in std::mem::take(&mut outcome.still_failed) {
+            eprint!("  Fetching {}... ", zone);
+            match fetch_and_store(client, store, zone, start, end).await {
+                Ok(count) => {
+                    eprintln!("✓ {} prices", count);
+                    outcome.recovered_zones += 1;
+                    outcome.prices_stored += count;
+                }
+                Err(e) => {
+                    eprintln!("✗ {}", e);
+                    failed.push((zone, e));
+                }
+            }
+        }
+        outcome.still_failed = failed;
     }
 
-    let api_token = env::var("ENTSOE_API_TOKEN")
-        .map_err(|_| "ENTSOE_API_TOKEN environment variable not set")?;
+    outcome
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let (zone, hours) = args.zone_and_hours()?;
 
-    let db_path = &args[1];
+    let api_token = args.api_token(env::var("ENTSOE_API_TOKEN").ok())?;
 
-    let zones: Vec<BiddingZone> = if let Some(zone_code) = args.get(2) {
-        let zone = BiddingZone::from_code(zone_code).ok_or_else(|| {
-            format!(
-                "Invalid bidding zone: '{}'. Valid zones: FI, NO2, SE3, DE, FR, etc.",
-                zone_code
-            )
-        })?;
-        vec![zone]
-    } else {
-        BiddingZone::all_zones()
-    };
+    let db_path = &args.database_path;
+    let retry_attempts = args.retry_failed;
 
-    let hours: i64 = if zones.len() == 1 {
-        args.get(3).and_then(|s| s.parse().ok()).unwrap_or(24)
-    } else {
-        args.get(2).and_then(|s| s.parse().ok()).unwrap_or(24)
+    let zones: Vec<BiddingZone> = match zone {
+        Some(zone) => vec![zone],
+        None => BiddingZone::all_zones(),
     };
 
     eprintln!("Opening database: {}", db_path);
     let conn = Connection::open(db_path)?;
 
     eprintln!("Initializing database schema...");
-    init_database(&conn)?;
+    init_schema(&conn)?;
 
     let client = EntsoeClient::new(api_token);
 
@@ -142,25 +284,35 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     for zone in zones {
         eprint!("  Fetching {}... ", zone);
 
-        match client.get_day_ahead_prices(zone, start, end).await {
-            Ok(price_doc) => match store_prices(&conn, zone, &price_doc) {
-                Ok(_) => {
-                    eprintln!("✓ {} prices", price_doc.prices.len());
-                    total_prices += price_doc.prices.len();
-                    successful_zones += 1;
-                }
-                Err(e) => {
-                    eprintln!("✗ Database error: {}", e);
-                    failed_zones.push((zone, format!("Database error: {}", e)));
-                }
-            },
+        match fetch_and_store(&client, &conn, zone, start, end).await {
+            Ok(count) => {
+                eprintln!("✓ {} prices", count);
+                total_prices += count;
+                successful_zones += 1;
+            }
             Err(e) => {
                 eprintln!("✗ {}", e);
-                failed_zones.push((zone, e.to_string()));
+                failed_zones.push((zone, e));
             }
         }
     }
 
+    if retry_attempts > 0 && !failed_zones.is_empty() {
+        let outcome = retry_failed_zones(
+            &client,
+            &conn,
+            failed_zones,
+            retry_attempts,
+            RETRY_DELAY,
+            start,
+            end,
+        )
+        .await;
+        successful_zones += outcome.recovered_zones;
+        total_prices += outcome.prices_stored;
+        failed_zones = outcome.still_failed;
+    }
+
     eprintln!();
     eprintln!("Summary:");
     eprintln!("  Successful: {} zones", successful_zones);
@@ -173,5 +325,231 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         }
     }
 
+    if args.summary_json {
+        let summary = RunSummary::new(successful_zones, total_prices, &failed_zones);
+        println!("{}", serde_json::to_string(&summary)?);
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    #[test]
+    fn test_args() {
+        let args =
+            Args::try_parse_from(["entsoe-fetch", "prices.db", "--zone", "FI", "--hours", "48"])
+                .unwrap();
+        assert_eq!(args.zone_and_hours(), Ok((Some(BiddingZone::FI), 48)));
+        assert_eq!(args.retry_failed, 0);
+        assert!(!args.summary_json);
+
+        let args = Args::try_parse_from(["entsoe-fetch", "prices.db"]).unwrap();
+        assert_eq!(args.zone_and_hours(), Ok((None, 24)));
+    }
+
+    #[test]
+    fn test_api_token_precedence() {
+        let path = env::temp_dir().join(format!("entsoe-fetch-token-{}", std::process::id()));
+        std::fs::write(&path, "from-file\n").unwrap();
+        let path_arg = path.to_str().unwrap();
+        let env_token = || Some("from-env".to_string());
+
+        let args =
+            Args::try_parse_from(["entsoe-fetch", "prices.db", "--token-file", path_arg]).unwrap();
+        assert_eq!(args.api_token(env_token()), Ok("from-file".to_string()));
+
+        let args = Args::try_parse_from([
+            "entsoe-fetch",
+            "prices.db",
+            "--token-file",
+            path_arg,
+            "--api-token",
+            "from-flag",
+        ])
+        .unwrap();
+        assert_eq!(args.api_token(env_token()), Ok("from-flag".to_string()));
+
+        let args = Args::try_parse_from(["entsoe-fetch", "prices.db"]).unwrap();
+        assert_eq!(args.api_token(env_token()), Ok("from-env".to_string()));
+        assert!(args.api_token(None).is_err());
+
+        std::fs::remove_file(&path).unwrap();
+        let args =
+            Args::try_parse_from(["entsoe-fetch", "prices.db", "--token-file", path_arg]).unwrap();
+        // A missing file is an error rather than a fallback to the environment
+        assert!(args.api_token(env_token()).is_err());
+    }
+
+    fn positional(args: &[&str]) -> Result<(Option<BiddingZone>, i64), String> {
+        let argv = ["entsoe-fetch", "prices.db"].iter().chain(args);
+        Args::try_parse_from(argv)
+            .map_err(|e| e.to_string())?
+            .zone_and_hours()
+    }
+
+    #[test]
+    fn test_run_summary_json() {
+        let failed = vec![(BiddingZone::SE1, "API returned status 503".to_string())];
+
+        let summary = serde_json::to_value(RunSummary::new(2, 96, &failed)).unwrap();
+
+        assert_eq!(
+            summary,
+            serde_json::json!({
+                "successful": 2,
+                "total_prices": 96,
+                "failed": [{"zone": "SE1", "error": "API returned status 503"}]
+            })
+        );
+    }
+
+    #[test]
+    fn test_positional_zone_and_hours() {
+        // Hours alone means all zones, not an invalid zone code "48"
+        assert_eq!(positional(&["48"]), Ok((None, 48)));
+        assert_eq!(positional(&["FI"]), Ok((Some(BiddingZone::FI), 24)));
+        assert_eq!(positional(&["FI", "48"]), Ok((Some(BiddingZone::FI), 48)));
+        assert_eq!(
+            positional(&["48", "--zone", "NO2"]),
+            Ok((Some(BiddingZone::NO2), 48))
+        );
+
+        assert!(positional(&["XX"]).is_err());
+        assert!(positional(&["FI", "abc"]).is_err());
+        assert!(positional(&["FI", "--zone", "NO2"]).is_err());
+        assert!(positional(&["48", "--hours", "12"]).is_err());
+        assert!(positional(&["FI", "48", "--hours", "12"]).is_err());
+        assert!(positional(&["48", "12"]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_args_error() {
+        let err =
+            Args::try_parse_from(["entsoe-fetch", "prices.db", "--hours", "abc"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+
+        let err = Args::try_parse_from(["entsoe-fetch", "prices.db", "--zone", "XX"]).unwrap_err();
+        assert_eq!(err.kind(), clap::error::ErrorKind::ValueValidation);
+
+        assert!(Args::try_parse_from(["entsoe-fetch", "prices.db", "FI", "48", "extra"]).is_err());
+    }
+
+    /// Answers 503 for the first `failures` requests, then serves the fixture.
+    struct FlakyTransport {
+        failures: usize,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl HttpTransport for FlakyTransport {
+        async fn get(&self, _url: url::Url) -> entsoe::Result<(reqwest::StatusCode, bytes::Bytes)> {
+            if self.calls.fetch_add(1, Ordering::SeqCst) < self.failures {
+                return Ok((
+                    reqwest::StatusCode::SERVICE_UNAVAILABLE,
+                    bytes::Bytes::new(),
+                ));
+            }
+            let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+            Ok((reqwest::StatusCode::OK, bytes::Bytes::from_static(xml)))
+        }
+    }
+
+    fn flaky_client(failures: usize) -> (EntsoeClient<FlakyTransport>, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let transport = FlakyTransport {
+            failures,
+            calls: Arc::clone(&calls),
+        };
+        (EntsoeClient::with_transport("test-token", transport), calls)
+    }
+
+    #[tokio::test]
+    async fn test_retry_failed_zones_recovers() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        let start = Utc::now();
+        let end = start + Duration::hours(24);
+
+        // The first pass already used up the single failure
+        let (client, calls) = flaky_client(1);
+        let first = fetch_and_store(&client, &conn, BiddingZone::FI, start, end).await;
+        assert!(first.is_err());
+
+        let outcome = retry_failed_zones(
+            &client,
+            &conn,
+            vec![(BiddingZone::FI, first.unwrap_err())],
+            3,
+            std::time::Duration::ZERO,
+            start,
+            end,
+        )
+        .await;
+
+        assert_eq!(outcome.recovered_zones, 1);
+        assert!(outcome.prices_stored > 0);
+        assert!(outcome.still_failed.is_empty());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    /// Records what would be stored instead of writing anywhere.
+    #[derive(Default)]
+    struct RecordingStore {
+        stored: RefCell<Vec<(BiddingZone, usize)>>,
+    }
+
+    impl PriceStore for RecordingStore {
+        async fn store(
+            &self,
+            zone: BiddingZone,
+            doc: &entsoe::PriceDocument,
+        ) -> entsoe::Result<usize> {
+            self.stored.borrow_mut().push((zone, doc.prices.len()));
+            Ok(doc.prices.len())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_and_store_any_price_store() {
+        let store = RecordingStore::default();
+        let start = Utc::now();
+        let end = start + Duration::hours(24);
+
+        let (client, _) = flaky_client(0);
+        let count = fetch_and_store(&client, &store, BiddingZone::SE3, start, end)
+            .await
+            .unwrap();
+
+        assert!(count > 0);
+        assert_eq!(*store.stored.borrow(), vec![(BiddingZone::SE3, count)]);
+    }
+
+    #[tokio::test]
+    async fn test_retry_failed_zones_gives_up() {
+        let store = RecordingStore::default();
+        let start = Utc::now();
+        let end = start + Duration::hours(24);
+
+        let (client, calls) = flaky_client(usize::MAX);
+        let outcome = retry_failed_zones(
+            &client,
+            &store,
+            vec![(BiddingZone::FI, "first pass".to_string())],
+            2,
+            std::time::Duration::ZERO,
+            start,
+            end,
+        )
+        .await;
+
+        assert_eq!(outcome.recovered_zones, 0);
+        assert_eq!(outcome.still_failed.len(), 1);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(store.stored.borrow().is_empty());
+    }
+}
@@ -0,0 +1,182 @@
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use entsoe::PriceDocument;
+use entsoe::storage::init_schema;
+use rusqlite::{Connection, params};
+use std::io::{self, BufRead};
+
+/// Imports prices as CSV from stdin into a SQLite database.
+#[derive(Debug, Parser)]
+#[command(
+    version,
+    after_help = "Examples:
+  entsoe-import prices.db < all_prices.csv
+  entsoe-csv old.db --zone FI | entsoe-import new.db
+
+Input: CSV on stdin with columns: timestamp,price_per_kwh,currency,price_area
+Note: Malformed lines are skipped; writes are idempotent"
+)]
+struct Args {
+    /// Path to SQLite database file (created if missing)
+    database_path: String,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct ImportStats {
+    imported: usize,
+    skipped: usize,
+}
+
+/// Validates one CSV line, returning the row in the form stored by entsoe-fetch.
+fn parse_line(line: &str) -> Result<(String, String, String, String), String> {
+    let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+    let [timestamp, price, currency, price_area] = fields[..] else {
+        return Err(format!("expected 4 fields, got {}", fields.len()));
+    };
+
+    // Stored in UTC like entsoe-fetch does, so the same instant keeps the same key and rows
+    // sort by time as text
+    let timestamp = DateTime::parse_from_rfc3339(timestamp)
+        .map_err(|e| format!("invalid timestamp '{}': {}", timestamp, e))?
+        .with_timezone(&Utc);
+    let price: f64 = price
+        .parse()
+        .ok()
+        .filter(|price: &f64| price.is_finite())
+        .ok_or_else(|| format!("invalid price '{}'", price))?;
+    if currency.len() != 3 || !currency.chars().all(|c| c.is_ascii_uppercase()) {
+        return Err(format!("invalid currency '{}'", currency));
+    }
+    if !(2..=8).contains(&price_area.len()) {
+        return Err(format!("invalid price area '{}'", price_area));
+    }
+
+    Ok((
+        timestamp.to_rfc3339(),
+        format!("{:.5}", price),
+        currency.to_string(),
+        price_area.to_string(),
+    ))
+}
+
+fn import_csv(
+    conn: &Connection,
+    reader: impl BufRead,
+) -> Result<ImportStats, Box<dyn std::error::Error>> {
+    let mut stmt = conn.prepare(
+        "INSERT OR REPLACE INTO prices (timestamp, price, currency, price_area)
+         VALUES (?1, ?2, ?3, ?4)",
+    )?;
+
+    let mut stats = ImportStats::default();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        let line_number = index + 1;
+
+        if line.trim().is_empty() || (line_number == 1 && line.trim() == PriceDocument::CSV_HEADER)
+        {
+            continue;
+        }
+
+        match parse_line(&line) {
+            Ok((timestamp, price, currency, price_area)) => {
+                stmt.execute(params![timestamp, price, currency, price_area])?;
+                stats.imported += 1;
+            }
+            Err(e) => {
+                eprintln!("  Skipping line {}: {}", line_number, e);
+                stats.skipped += 1;
+            }
+        }
+    }
+
+    Ok(stats)
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = Args::parse();
+    let db_path = &args.database_path;
+
+    eprintln!("Opening database: {}", db_path);
+    let conn = Connection::open(db_path)?;
+    init_schema(&conn)?;
+
+    let stdin = io::stdin();
+    let stats = import_csv(&conn, stdin.lock())?;
+
+    eprintln!();
+    eprintln!("Summary:");
+    eprintln!("  Imported: {} rows", stats.imported);
+    eprintln!("  Skipped: {} malformed lines", stats.skipped);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_csv() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+
+        let csv = "timestamp,price_per_kwh,currency,price_area
+2025-10-12T22:00:00+00:00,0.00267,EUR,FI
+2025-10-12T22:15:00+00:00,0.00235,EUR,FI
+2025-10-12T22:00:00+00:00,0.04120,EUR,SE3
+not-a-timestamp,0.1,EUR,FI
+2025-10-12T22:30:00+00:00,cheap,EUR,FI
+2025-10-12T22:45:00+00:00,0.1,eur,FI
+2025-10-12T23:00:00+00:00,0.1,EUR
+2025-10-12T23:15:00+00:00,inf,EUR,FI
+2025-10-12T23:30:00+00:00,NaN,EUR,FI
+";
+
+        let stats = import_csv(&conn, csv.as_bytes()).unwrap();
+        assert_eq!(
+            stats,
+            ImportStats {
+                imported: 3,
+                skipped: 6
+            }
+        );
+
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM prices", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 3);
+
+        // Re-importing the same rows replaces them rather than duplicating
+        import_csv(&conn, csv.as_bytes()).unwrap();
+        let fi_count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM prices WHERE price_area = 'FI'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(fi_count, 2);
+    }
+
+    #[test]
+    fn test_import_normalizes_offsets_to_utc() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+
+        // The same instant written with two offsets is one row
+        let csv = "2025-10-13T01:00:00+03:00,0.00267,EUR,FI
+2025-10-12T22:00:00+00:00,0.00300,EUR,FI
+";
+        import_csv(&conn, csv.as_bytes()).unwrap();
+
+        let (timestamp, price): (String, String) = conn
+            .query_row("SELECT timestamp, price FROM prices", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!(timestamp, "2025-10-12T22:00:00+00:00");
+        assert_eq!(price, "0.00300");
+    }
+}
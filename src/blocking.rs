@@ -0,0 +1,278 @@
+//! Synchronous client for callers without an async runtime, such as scripts and simple CLIs.
+//!
+//! Mirrors the day-ahead price methods of [`EntsoeClient`]. URLs, token redaction, error
+//! handling, retries, caching, metrics and parsing are shared with it; only the HTTP transport
+//! and the way retries wait differ.
+
+use chrono::{DateTime, Utc};
+use reqwest::header::ACCEPT;
+use url::Url;
+
+use crate::bidding_zone::BiddingZone;
+use crate::client::{
+    EntsoeClient, EntsoeClientBuilder, day_ahead_cache_key, warn_if_beyond_day_ahead_horizon,
+};
+use crate::error::Result;
+use crate::models::PriceDocument;
+use crate::parser::parse_day_ahead_prices;
+
+/// Blocking client for the ENTSO-E Transparency Platform API, backed by
+/// `reqwest::blocking::Client`.
+///
+/// Must not be used from within an async runtime; use [`EntsoeClient`] there.
+pub struct BlockingEntsoeClient {
+    /// Builds URLs and turns responses into results; never sends anything itself.
+    requests: EntsoeClient<()>,
+    http_client: reqwest::blocking::Client,
+    accept: String,
+}
+
+impl BlockingEntsoeClient {
+    /// Creates a client with the defaults of [`EntsoeClient::new`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the underlying HTTP client cannot be constructed, like
+    /// `reqwest::blocking::Client::new`.
+    pub fn new(api_token: impl Into<String>) -> Self {
+        EntsoeClientBuilder::new(api_token)
+            .build_blocking()
+            .expect("default blocking client")
+    }
+
+    /// Same builder as [`EntsoeClient::builder`]; finish it with
+    /// [`build_blocking`](EntsoeClientBuilder::build_blocking).
+    pub fn builder(api_token: impl Into<String>) -> EntsoeClientBuilder {
+        EntsoeClientBuilder::new(api_token)
+    }
+
+    pub(crate) fn from_parts(
+        requests: EntsoeClient<()>,
+        http_client: reqwest::blocking::Client,
+        accept: String,
+    ) -> Self {
+        Self {
+            requests,
+            http_client,
+            accept,
+        }
+    }
+
+    /// Fetches day-ahead prices as raw XML bytes. Times must be in UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, URL construction fails, or time range is invalid.
+    pub fn fetch_day_ahead_prices(
+        &self,
+        bidding_zone: BiddingZone,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<bytes::Bytes> {
+        #[cfg(feature = "metrics")]
+        let started = std::time::Instant::now();
+
+        let result = self
+            .requests
+            .day_ahead_prices_url(bidding_zone, period_start, period_end)
+            .and_then(|url| {
+                warn_if_beyond_day_ahead_horizon(period_end, Utc::now());
+                self.fetch_xml(&url)
+            });
+
+        #[cfg(feature = "metrics")]
+        crate::telemetry::record_request("day_ahead_prices", started.elapsed(), &result);
+        result
+    }
+
+    /// Fetches and parses day-ahead prices. Times must be in UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, XML parsing fails, or time range is invalid.
+    pub fn get_day_ahead_prices(
+        &self,
+        bidding_zone: BiddingZone,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<PriceDocument> {
+        let cache_key = day_ahead_cache_key(bidding_zone, period_start, period_end);
+        if let Some(doc) = self.requests.cache().and_then(|c| c.get(&cache_key)) {
+            return Ok(doc);
+        }
+
+        let xml = self.fetch_day_ahead_prices(bidding_zone, period_start, period_end)?;
+        let doc = parse_day_ahead_prices(&xml)?;
+
+        if let Some(cache) = self.requests.cache() {
+            cache.insert(cache_key, doc.clone());
+        }
+        Ok(doc)
+    }
+
+    fn fetch_xml(&self, url: &Url) -> Result<bytes::Bytes> {
+        let mut attempts = 1;
+        loop {
+            match self.fetch_xml_once(url.clone()) {
+                Err(e) => {
+                    let delay = self.requests.retry_delay(attempts, e)?;
+                    attempts += 1;
+                    std::thread::sleep(delay);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn fetch_xml_once(&self, url: Url) -> Result<bytes::Bytes> {
+        let redacted_url = self.requests.redact_url(&url);
+        let send = || -> reqwest::Result<_> {
+            let response = self
+                .http_client
+                .get(url)
+                .header(ACCEPT, &self.accept)
+                .send()?;
+            let status = response.status();
+            Ok((status, response.bytes()?))
+        };
+        let (status, body) = send().map_err(|e| self.requests.redact_http_error(e))?;
+        self.requests.check_response(status, body, redacted_url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+    use crate::error::EntsoeError;
+
+    fn client(server: &mockito::Server) -> BlockingEntsoeClient {
+        BlockingEntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .build_blocking()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_get_day_ahead_prices() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("documentType".into(), "A44".into()),
+                mockito::Matcher::UrlEncoded("in_Domain".into(), "10YFI-1--------U".into()),
+                mockito::Matcher::UrlEncoded("securityToken".into(), "test-token".into()),
+            ]))
+            .match_header("accept", "application/xml")
+            .with_body(include_str!("../tests/fixtures/day_ahead_prices_fi.xml"))
+            .create();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let doc = client(&server)
+            .get_day_ahead_prices(BiddingZone::FI, start, end)
+            .unwrap();
+
+        mock.assert();
+        assert_eq!(doc.currency, "EUR");
+        assert!(!doc.prices.is_empty());
+    }
+
+    #[test]
+    fn test_api_error_is_redacted() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_status(400)
+            .with_body("bad token test-token")
+            .create();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let err = client(&server)
+            .fetch_day_ahead_prices(BiddingZone::FI, start, end)
+            .unwrap_err();
+
+        match err {
+            EntsoeError::ApiError { status, url, body } => {
+                assert_eq!(status, 400);
+                assert!(url.contains("securityToken=***"));
+                assert_eq!(body, "bad token ***");
+            }
+            other => panic!("Expected ApiError, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_retries_then_succeeds() {
+        let mut server = mockito::Server::new();
+        let unavailable = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .with_body("Service Unavailable")
+            .expect(1)
+            .create();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+        let client = BlockingEntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .retry(2, std::time::Duration::ZERO)
+            .cache(std::time::Duration::from_mins(1))
+            .build_blocking()
+            .unwrap();
+
+        let ok = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_body(include_str!("../tests/fixtures/day_ahead_prices_fi.xml"))
+            .expect(1)
+            .create();
+
+        let doc = client
+            .get_day_ahead_prices(BiddingZone::FI, start, end)
+            .unwrap();
+        // The second call is served from the cache
+        let cached = client
+            .get_day_ahead_prices(BiddingZone::FI, start, end)
+            .unwrap();
+
+        unavailable.assert();
+        ok.assert();
+        assert_eq!(doc, cached);
+    }
+
+    #[test]
+    fn test_retries_exhausted_wraps_last_error() {
+        let mut server = mockito::Server::new();
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .with_body("Service Unavailable")
+            .expect(3)
+            .create();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+        let client = BlockingEntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .retry(3, std::time::Duration::ZERO)
+            .build_blocking()
+            .unwrap();
+
+        let err = client
+            .fetch_day_ahead_prices(BiddingZone::FI, start, end)
+            .unwrap_err();
+
+        mock.assert();
+        match err {
+            EntsoeError::RetriesExhausted { attempts, last } => {
+                assert_eq!(attempts, 3);
+                assert!(matches!(*last, EntsoeError::ApiError { status: 503, .. }));
+            }
+            other => panic!("Expected RetriesExhausted, got {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::models::PriceDocument;
+
+/// Identifies a request by `(eic_code, periodStart, periodEnd, documentType)`.
+pub(crate) type CacheKey = (&'static str, String, String, &'static str);
+
+/// In-memory TTL cache of parsed price documents.
+pub(crate) struct PriceCache {
+    ttl: Duration,
+    entries: Mutex<HashMap<CacheKey, (Instant, PriceDocument)>>,
+}
+
+impl PriceCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn get(&self, key: &CacheKey) -> Option<PriceDocument> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        match entries.get(key) {
+            Some((stored_at, doc)) if stored_at.elapsed() < self.ttl => Some(doc.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn insert(&self, key: CacheKey, doc: PriceDocument) {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(key, (Instant::now(), doc));
+    }
+
+    pub fn clear(&self) {
+        self.entries
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clear();
+    }
+}
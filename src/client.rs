@@ -1,59 +1,460 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, DurationRound, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use reqwest::Client;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, USER_AGENT};
 use url::Url;
 
 use crate::bidding_zone::BiddingZone;
+use crate::cache::{CacheKey, PriceCache};
 use crate::error::{EntsoeError, Result};
-use crate::models::PriceDocument;
-use crate::parser::parse_day_ahead_prices;
+use crate::models::{
+    FlowDocument, GenerationDocument, GenerationForecastDocument, InstalledCapacityDocument,
+    LoadDocument, PriceDocument,
+};
+use crate::params::{DocumentType, Domain, ProcessType};
+use crate::parser::{
+    parse_actual_generation, parse_day_ahead_prices, parse_generation_forecast,
+    parse_installed_capacity, parse_physical_flows, parse_total_load,
+};
+use crate::transport::{HttpTransport, ReqwestTransport};
 
 const API_BASE_URL: &str = "https://web-api.tp.entsoe.eu/api";
+const DEFAULT_ACCEPT: &str = "application/xml";
+/// ENTSO-E rejects price queries spanning more than a year.
+const MAX_PRICE_QUERY_DAYS: i64 = 365;
+/// Longest range the API accepts in a single query.
+const MAX_QUERY_DAYS: i64 = 372;
+/// Day-ahead prices are published around noon for the next day, so nothing further out exists.
+const DAY_AHEAD_HORIZON_HOURS: i64 = 36;
+
+/// Reads an API token from the file at `path`, trimmed of surrounding whitespace such as a
+/// trailing newline.
+///
+/// # Errors
+///
+/// Returns error if the file cannot be read or holds only whitespace.
+pub fn read_api_token(path: impl AsRef<std::path::Path>) -> std::io::Result<String> {
+    let path = path.as_ref();
+    let token = std::fs::read_to_string(path)?.trim().to_string();
+    if token.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("API token file {} is empty", path.display()),
+        ));
+    }
+    Ok(token)
+}
 
 /// Client for interacting with the ENTSO-E Transparency Platform API.
-pub struct EntsoeClient {
+///
+/// Generic over the [`HttpTransport`] so tests can inject canned responses; the default talks to
+/// the network through `reqwest`.
+pub struct EntsoeClient<T = ReqwestTransport> {
     api_token: String,
-    http_client: Client,
+    base_url: String,
+    transport: T,
+    cache: Option<PriceCache>,
+    redact_token: bool,
+    max_attempts: u32,
+    retry_delay: std::time::Duration,
 }
 
-impl EntsoeClient {
+/// Builder for [`EntsoeClient`] when the defaults of [`EntsoeClient::new`] don't fit.
+pub struct EntsoeClientBuilder {
+    api_token: String,
+    base_url: String,
+    accept: String,
+    cache_ttl: Option<std::time::Duration>,
+    redact_token: bool,
+    user_agent: Option<String>,
+    default_headers: Vec<(String, String)>,
+    proxies: Vec<reqwest::Proxy>,
+    max_attempts: u32,
+    retry_delay: std::time::Duration,
+}
+
+impl EntsoeClientBuilder {
     pub fn new(api_token: impl Into<String>) -> Self {
         Self {
             api_token: api_token.into(),
-            http_client: Client::new(),
+            base_url: API_BASE_URL.to_string(),
+            accept: DEFAULT_ACCEPT.to_string(),
+            cache_ttl: None,
+            redact_token: true,
+            user_agent: None,
+            default_headers: Vec::new(),
+            proxies: Vec::new(),
+            max_attempts: 1,
+            retry_delay: std::time::Duration::ZERO,
         }
     }
 
-    /// Fetches day-ahead prices as raw XML bytes. Times must be in UTC.
+    /// Replaces the API token with the one [read](read_api_token) from the file at `path`.
+    ///
+    /// Keeps the token out of process listings and environment dumps.
     ///
     /// # Errors
     ///
-    /// Returns error if the HTTP request fails, URL construction fails, or time range is invalid.
-    pub async fn fetch_day_ahead_prices(
+    /// Returns error if the file cannot be read or holds only whitespace.
+    pub fn api_token_from_file(
+        mut self,
+        path: impl AsRef<std::path::Path>,
+    ) -> std::io::Result<Self> {
+        self.api_token = read_api_token(path)?;
+        Ok(self)
+    }
+
+    /// Overrides the API endpoint, e.g. for a caching proxy or a mock server.
+    #[must_use]
+    pub fn base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    /// Overrides the `Accept` header sent with every request (default `application/xml`).
+    #[must_use]
+    pub fn accept(mut self, accept: impl Into<String>) -> Self {
+        self.accept = accept.into();
+        self
+    }
+
+    /// Caches parsed price documents in memory for `ttl`.
+    ///
+    /// Identical requests (same zone, period and document type) within the TTL are served from
+    /// the cache without an HTTP call.
+    #[must_use]
+    pub fn cache(mut self, ttl: std::time::Duration) -> Self {
+        self.cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Replaces the security token with `***` in error messages and URLs carried by errors
+    /// (default `true`).
+    #[must_use]
+    pub fn redact_token_in_errors(mut self, redact: bool) -> Self {
+        self.redact_token = redact;
+        self
+    }
+
+    /// Makes up to `max_attempts` requests, `delay` apart, while requests fail with a
+    /// [retryable](EntsoeError::is_retryable) error (default: a single attempt).
+    ///
+    /// When every attempt fails, the last error is returned wrapped in
+    /// [`EntsoeError::RetriesExhausted`].
+    #[must_use]
+    pub fn retry(mut self, max_attempts: u32, delay: std::time::Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request (none by default).
+    #[must_use]
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Adds a header sent with every request. Can be called repeatedly.
+    #[must_use]
+    pub fn default_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.default_headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Routes requests through `proxy`. Can be called repeatedly.
+    ///
+    /// Without an explicit proxy, the `HTTPS_PROXY`, `HTTP_PROXY`, `ALL_PROXY` and `NO_PROXY`
+    /// environment variables are honored. Setting a proxy here replaces them entirely; use
+    /// [`reqwest::Proxy::no_proxy`] with [`reqwest::NoProxy::from_env`] to keep the `NO_PROXY`
+    /// exclusions.
+    #[must_use]
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Builds the client.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a configured header is invalid or the underlying HTTP client cannot be
+    /// constructed.
+    pub fn build(self) -> Result<EntsoeClient> {
+        let mut http_client = Client::builder().default_headers(self.headers()?);
+        for proxy in self.proxies.iter().cloned() {
+            http_client = http_client.proxy(proxy);
+        }
+        let http_client = http_client.build()?;
+        let transport = ReqwestTransport::new(http_client, &self.accept);
+        Ok(self.into_client(transport))
+    }
+
+    /// Builds a [`BlockingEntsoeClient`](crate::blocking::BlockingEntsoeClient) with the same
+    /// settings, including the cache and retries.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if a configured header is invalid or the underlying HTTP client cannot be
+    /// constructed.
+    #[cfg(feature = "blocking")]
+    pub fn build_blocking(self) -> Result<crate::blocking::BlockingEntsoeClient> {
+        let mut http_client = reqwest::blocking::Client::builder().default_headers(self.headers()?);
+        for proxy in self.proxies.iter().cloned() {
+            http_client = http_client.proxy(proxy);
+        }
+        let http_client = http_client.build()?;
+        let accept = self.accept.clone();
+        Ok(crate::blocking::BlockingEntsoeClient::from_parts(
+            self.into_client(()),
+            http_client,
+            accept,
+        ))
+    }
+
+    /// Headers sent with every request: the default headers and the `User-Agent`.
+    fn headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        for (name, value) in &self.default_headers {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|e| EntsoeError::InvalidHeader(format!("{name}: {e}")))?;
+            let header_value = HeaderValue::from_str(value)
+                .map_err(|e| EntsoeError::InvalidHeader(format!("{name}: {e}")))?;
+            headers.append(header_name, header_value);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            let user_agent = HeaderValue::from_str(user_agent)
+                .map_err(|e| EntsoeError::InvalidHeader(format!("User-Agent: {e}")))?;
+            headers.insert(USER_AGENT, user_agent);
+        }
+        Ok(headers)
+    }
+
+    fn into_client<T>(self, transport: T) -> EntsoeClient<T> {
+        EntsoeClient {
+            api_token: self.api_token,
+            base_url: self.base_url,
+            transport,
+            cache: self.cache_ttl.map(PriceCache::new),
+            redact_token: self.redact_token,
+            max_attempts: self.max_attempts,
+            retry_delay: self.retry_delay,
+        }
+    }
+}
+
+impl EntsoeClient {
+    pub fn new(api_token: impl Into<String>) -> Self {
+        let builder = EntsoeClientBuilder::new(api_token);
+        let transport = ReqwestTransport::new(Client::new(), &builder.accept);
+        builder.into_client(transport)
+    }
+
+    pub fn builder(api_token: impl Into<String>) -> EntsoeClientBuilder {
+        EntsoeClientBuilder::new(api_token)
+    }
+}
+
+/// Request building and response handling, shared with the blocking client.
+impl<T> EntsoeClient<T> {
+    /// The URL [`fetch_day_ahead_prices`](EntsoeClient::fetch_day_ahead_prices) would request,
+    /// without sending anything. Times must be in UTC.
+    ///
+    /// The URL carries the security token in its `securityToken` query parameter, so redact it
+    /// before logging.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if URL construction fails or time range is invalid.
+    pub fn day_ahead_prices_url(
         &self,
         bidding_zone: BiddingZone,
         period_start: DateTime<Utc>,
         period_end: DateTime<Utc>,
+    ) -> Result<Url> {
+        validate_time_range(period_start, period_end)?;
+        self.build_url(
+            DocumentType::DayAheadPrices,
+            &[
+                ("in_Domain", bidding_zone.eic_code()),
+                ("out_Domain", bidding_zone.eic_code()),
+            ],
+            period_start,
+            period_end,
+        )
+    }
+
+    /// The in-memory price cache, if [`EntsoeClientBuilder::cache`] enabled it.
+    pub(crate) fn cache(&self) -> Option<&PriceCache> {
+        self.cache.as_ref()
+    }
+
+    /// Decides what follows failed attempt number `attempts`: the delay before retrying, or the
+    /// error to give up with, wrapped in [`EntsoeError::RetriesExhausted`] once retries ran out.
+    pub(crate) fn retry_delay(
+        &self,
+        attempts: u32,
+        error: EntsoeError,
+    ) -> Result<std::time::Duration> {
+        if !error.is_retryable() || self.max_attempts <= 1 {
+            return Err(error);
+        }
+        if attempts >= self.max_attempts {
+            return Err(EntsoeError::RetriesExhausted {
+                attempts,
+                last: Box::new(error),
+            });
+        }
+        #[cfg(feature = "tracing")]
+        tracing::warn!(attempt = attempts, max_attempts = self.max_attempts, error = %error, "retrying request");
+        Ok(self.retry_delay)
+    }
+
+    /// Turns an HTTP response into the body, or the error its status and content stand for.
+    pub(crate) fn check_response(
+        &self,
+        status: reqwest::StatusCode,
+        body: bytes::Bytes,
+        redacted_url: String,
     ) -> Result<bytes::Bytes> {
-        if period_start >= period_end {
-            return Err(EntsoeError::InvalidTimeRange(
-                "period_start must be before period_end".to_string(),
-            ));
+        if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(EntsoeError::RateLimited { url: redacted_url });
+        }
+
+        if matches!(
+            status,
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN
+        ) && is_html(&body)
+        {
+            return Err(EntsoeError::Unauthorized {
+                status: status.as_u16(),
+                url: redacted_url,
+            });
+        }
+
+        if !status.is_success() {
+            let body = String::from_utf8_lossy(&body);
+            return Err(EntsoeError::ApiError {
+                status: status.as_u16(),
+                url: redacted_url,
+                body: self.redact(&body),
+            });
+        }
+
+        Ok(body)
+    }
+
+    pub(crate) fn redact(&self, text: &str) -> String {
+        if self.redact_token && !self.api_token.is_empty() {
+            text.replace(&self.api_token, "***")
+        } else {
+            text.to_string()
+        }
+    }
+
+    /// `url` with its `securityToken` query parameter replaced by `***`, for errors and logs.
+    ///
+    /// Rewrites the decoded query pair rather than the text, since the token appears
+    /// percent-encoded in the URL when it contains characters such as `+`, `/` or `=`.
+    pub(crate) fn redact_url(&self, url: &Url) -> String {
+        if !self.redact_token {
+            return url.to_string();
+        }
+        redact_query_token(url).to_string()
+    }
+
+    /// reqwest includes the request URL, and with it the token, in its error messages.
+    pub(crate) fn redact_http_error(&self, error: reqwest::Error) -> EntsoeError {
+        if !self.redact_token {
+            return error.into();
+        }
+        let Some(url) = error.url() else {
+            return error.into();
+        };
+        let redacted = redact_query_token(url);
+        error.with_url(redacted).into()
+    }
+
+    /// Builds an API URL for `document_type` from endpoint-specific parameters, appending the
+    /// period and token.
+    fn build_url(
+        &self,
+        document_type: DocumentType,
+        params: &[(&str, &str)],
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<Url> {
+        let period_start = format_timestamp(period_start);
+        let period_end = format_timestamp(period_end);
+
+        let mut all = vec![("documentType", document_type.code())];
+        all.extend_from_slice(params);
+        all.push(("periodStart", &period_start));
+        all.push(("periodEnd", &period_end));
+        self.build_raw_url(&all)
+    }
+
+    /// Appends `params` and the security token to the base URL.
+    fn build_raw_url(&self, params: &[(&str, &str)]) -> Result<Url> {
+        let mut url = Url::parse(&self.base_url)?;
+
+        {
+            let mut query = url.query_pairs_mut();
+            for (key, value) in params {
+                query.append_pair(key, value);
+            }
+            query.append_pair("securityToken", &self.api_token);
         }
 
-        let url = self.build_day_ahead_prices_url(bidding_zone, period_start, period_end)?;
+        Ok(url)
+    }
+}
+
+impl<T: HttpTransport> EntsoeClient<T> {
+    /// Creates a client that performs requests through a custom transport.
+    pub fn with_transport(api_token: impl Into<String>, transport: T) -> Self {
+        EntsoeClientBuilder::new(api_token).into_client(transport)
+    }
+
+    /// Drops all cached price documents. Does nothing if caching is disabled.
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+    }
 
-        let response = self.http_client.get(url).send().await?;
+    /// Fetches day-ahead prices as raw XML bytes. Times must be in UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, URL construction fails, or time range is invalid.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(
+            name = "fetch_day_ahead_prices",
+            skip(self),
+            fields(zone = bidding_zone.code(), %period_start, %period_end)
+        )
+    )]
+    pub async fn fetch_day_ahead_prices(
+        &self,
+        bidding_zone: BiddingZone,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<bytes::Bytes> {
+        #[cfg(feature = "metrics")]
+        let started = std::time::Instant::now();
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let body = response.text().await.unwrap_or_default();
-            return Err(EntsoeError::ApiError(format!(
-                "API returned status {status}: {body}"
-            )));
+        let result = async {
+            let url = self.day_ahead_prices_url(bidding_zone, period_start, period_end)?;
+            warn_if_beyond_day_ahead_horizon(period_end, Utc::now());
+            self.fetch_xml(url).await
         }
+        .await;
 
-        let bytes = response.bytes().await?;
-        Ok(bytes)
+        #[cfg(feature = "metrics")]
+        crate::telemetry::record_request("day_ahead_prices", started.elapsed(), &result);
+        result
     }
 
     /// Fetches and parses day-ahead prices. Times must be in UTC.
@@ -67,66 +468,1941 @@ impl EntsoeClient {
         period_start: DateTime<Utc>,
         period_end: DateTime<Utc>,
     ) -> Result<PriceDocument> {
+        let cache_key = day_ahead_cache_key(bidding_zone, period_start, period_end);
+        if let Some(doc) = self.cache().and_then(|c| c.get(&cache_key)) {
+            return Ok(doc);
+        }
+
         let xml = self
             .fetch_day_ahead_prices(bidding_zone, period_start, period_end)
             .await?;
-        parse_day_ahead_prices(&xml)
+        let doc = parse_day_ahead_prices(&xml)?;
+
+        if let Some(cache) = self.cache() {
+            cache.insert(cache_key, doc.clone());
+        }
+        Ok(doc)
     }
 
-    fn build_day_ahead_prices_url(
+    /// Fetches and parses day-ahead prices for the calendar day `day` in `tz`, e.g. "today in
+    /// Helsinki".
+    ///
+    /// The range runs from local midnight to the next local midnight, see [`local_day_range`], so
+    /// it spans 23 hours on the day clocks spring forward and 25 hours on the day they fall back.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if local midnight doesn't exist in `tz`, the HTTP request fails, or XML
+    /// parsing fails.
+    pub async fn get_day_ahead_prices_for_local_day(
+        &self,
+        bidding_zone: BiddingZone,
+        day: NaiveDate,
+        tz: Tz,
+    ) -> Result<PriceDocument> {
+        let (period_start, period_end) = local_day_range(day, tz)?;
+        self.get_day_ahead_prices(bidding_zone, period_start, period_end)
+            .await
+    }
+
+    /// Fetches and parses day-ahead prices for an arbitrarily long range. Times must be in UTC.
+    ///
+    /// The range is split into chunks the API accepts, fetched sequentially and merged into one
+    /// document sorted by timestamp. Points appearing in more than one chunk are kept once.
+    ///
+    /// If a response's declared interval ends before its chunk does, the response is taken as
+    /// truncated and the remainder is requested from there, until the chunk is covered or the API
+    /// reports no further data.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if any chunk fails to fetch or parse, or time range is invalid.
+    pub async fn get_day_ahead_prices_range(
         &self,
         bidding_zone: BiddingZone,
         period_start: DateTime<Utc>,
         period_end: DateTime<Utc>,
-    ) -> Result<Url> {
-        let mut url = Url::parse(API_BASE_URL)?;
+    ) -> Result<PriceDocument> {
+        self.get_day_ahead_prices_chunked(
+            bidding_zone,
+            period_start,
+            period_end,
+            Duration::days(MAX_PRICE_QUERY_DAYS),
+        )
+        .await
+    }
 
-        {
-            let mut query = url.query_pairs_mut();
-            query.append_pair("documentType", "A44");
-            query.append_pair("in_Domain", bidding_zone.eic_code());
-            query.append_pair("out_Domain", bidding_zone.eic_code());
-            query.append_pair("periodStart", &format_timestamp(period_start));
-            query.append_pair("periodEnd", &format_timestamp(period_end));
-            query.append_pair("securityToken", &self.api_token);
+    /// Fetches and parses day-ahead prices for several zones, one after another, until `deadline`.
+    /// Times must be in UTC.
+    ///
+    /// Returns one result per zone, in the order given. Once the deadline passes, the request in
+    /// flight is abandoned and no new ones are started; those zones get [`EntsoeError::Timeout`].
+    pub async fn get_day_ahead_prices_multi_deadline(
+        &self,
+        zones: &[BiddingZone],
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        deadline: tokio::time::Instant,
+    ) -> Vec<(BiddingZone, Result<PriceDocument>)> {
+        let mut results = Vec::with_capacity(zones.len());
+        for &zone in zones {
+            let result = if tokio::time::Instant::now() >= deadline {
+                Err(EntsoeError::Timeout)
+            } else {
+                tokio::time::timeout_at(
+                    deadline,
+                    self.get_day_ahead_prices(zone, period_start, period_end),
+                )
+                .await
+                .unwrap_or(Err(EntsoeError::Timeout))
+            };
+            results.push((zone, result));
         }
+        results
+    }
 
-        Ok(url)
+    /// Polls day-ahead prices every `poll_interval` until `predicate` holds for a freshly
+    /// fetched document, and returns that document.
+    ///
+    /// Each poll covers the current hour up to the day-ahead horizon of 36 hours and bypasses the
+    /// cache. "No matching data" acknowledgements count as not published yet and keep polling.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EntsoeError::Timeout`] if `predicate` does not hold within `timeout`, or the
+    /// first other error a poll runs into.
+    pub async fn wait_for<F>(
+        &self,
+        bidding_zone: BiddingZone,
+        predicate: F,
+        poll_interval: std::time::Duration,
+        timeout: std::time::Duration,
+    ) -> Result<PriceDocument>
+    where
+        F: Fn(&PriceDocument) -> bool,
+    {
+        let poll = async {
+            loop {
+                let now = Utc::now();
+                let period_start = now.duration_trunc(Duration::hours(1)).unwrap_or(now);
+                let period_end = period_start + Duration::hours(DAY_AHEAD_HORIZON_HOURS);
+                let doc = self
+                    .fetch_day_ahead_prices(bidding_zone, period_start, period_end)
+                    .await
+                    .and_then(|xml| parse_day_ahead_prices(&xml));
+                match doc {
+                    Ok(doc) if predicate(&doc) => return Ok(doc),
+                    Ok(_) | Err(EntsoeError::Acknowledgement { .. }) => {}
+                    Err(e) => return Err(e),
+                }
+                tokio::time::sleep(poll_interval).await;
+            }
+        };
+        tokio::time::timeout(timeout, poll)
+            .await
+            .unwrap_or(Err(EntsoeError::Timeout))
     }
-}
 
-fn format_timestamp(dt: DateTime<Utc>) -> String {
-    dt.format("%Y%m%d%H%M").to_string()
-}
+    async fn get_day_ahead_prices_chunked(
+        &self,
+        bidding_zone: BiddingZone,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+        chunk: Duration,
+    ) -> Result<PriceDocument> {
+        // The API's range limit applies to each chunk's request, not the whole range
+        validate_period_order(period_start, period_end)?;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use chrono::TimeZone;
+        let mut merged: Option<PriceDocument> = None;
+        let mut chunk_start = period_start;
+        while chunk_start < period_end {
+            let chunk_end = (chunk_start + chunk).min(period_end);
+            // A truncated response declares an interval ending before the chunk does; continue
+            // from there. Its last point can end earlier without truncation, as A03 curves leave
+            // out trailing repeated prices.
+            let mut request_start = chunk_start;
+            while request_start < chunk_end {
+                let doc = match self
+                    .get_day_ahead_prices(bidding_zone, request_start, chunk_end)
+                    .await
+                {
+                    // The remainder may simply not be published yet
+                    Err(EntsoeError::Acknowledgement { .. }) if request_start > chunk_start => {
+                        break;
+                    }
+                    result => result?,
+                };
+                let covered_until = doc.period_end;
+                merged = Some(match merged {
+                    Some(merged) => merged.merge(doc),
+                    None => doc,
+                });
+                if covered_until <= request_start {
+                    break;
+                }
+                request_start = covered_until;
+                #[cfg(feature = "tracing")]
+                if request_start < chunk_end {
+                    tracing::debug!(
+                        %request_start,
+                        %chunk_end,
+                        "response ended before the chunk; fetching the remainder"
+                    );
+                }
+            }
+            chunk_start = chunk_end;
+        }
 
-    #[test]
-    fn test_format_timestamp() {
-        let dt = Utc.with_ymd_and_hms(2024, 1, 15, 14, 30, 0).unwrap();
-        assert_eq!(format_timestamp(dt), "202401151430");
+        merged.ok_or_else(|| EntsoeError::XmlParseError("No price points found".to_string()))
     }
 
-    #[test]
-    fn test_build_url() {
-        let client = EntsoeClient::new("test-token");
-        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
-        let end = Utc.with_ymd_and_hms(2024, 1, 16, 0, 0, 0).unwrap();
+    /// Fetches intraday prices as raw XML bytes. Times must be in UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, URL construction fails, or time range is invalid.
+    pub async fn fetch_intraday_prices(
+        &self,
+        bidding_zone: BiddingZone,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<bytes::Bytes> {
+        validate_time_range(period_start, period_end)?;
+        let url = self.build_intraday_prices_url(bidding_zone, period_start, period_end)?;
+        self.fetch_xml(url).await
+    }
 
-        let url = client
-            .build_day_ahead_prices_url(BiddingZone::DE, start, end)
-            .unwrap();
+    /// Fetches and parses intraday prices. Times must be in UTC.
+    ///
+    /// Intraday documents share the day-ahead format. Unlike day-ahead prices they are not
+    /// cached, since they keep changing during the day.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, XML parsing fails, or time range is invalid.
+    pub async fn get_intraday_prices(
+        &self,
+        bidding_zone: BiddingZone,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<PriceDocument> {
+        let xml = self
+            .fetch_intraday_prices(bidding_zone, period_start, period_end)
+            .await?;
+        parse_day_ahead_prices(&xml)
+    }
 
-        let url_str = url.as_str();
-        assert!(url_str.contains("documentType=A44"));
-        assert!(url_str.contains("in_Domain=10Y1001A1001A82H"));
-        assert!(url_str.contains("out_Domain=10Y1001A1001A82H"));
-        assert!(url_str.contains("periodStart=202401150000"));
-        assert!(url_str.contains("periodEnd=202401160000"));
-        assert!(url_str.contains("securityToken=test-token"));
+    /// Fetches the generation forecast as raw XML bytes. Times must be in UTC.
+    ///
+    /// `process_type` selects the horizon, e.g. [`ProcessType::DayAhead`] or
+    /// [`ProcessType::Intraday`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, URL construction fails, or time range is invalid.
+    pub async fn fetch_generation_forecast(
+        &self,
+        bidding_zone: BiddingZone,
+        process_type: ProcessType,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<bytes::Bytes> {
+        validate_time_range(period_start, period_end)?;
+        let url = self.build_generation_forecast_url(
+            bidding_zone,
+            process_type,
+            period_start,
+            period_end,
+        )?;
+        self.fetch_xml(url).await
+    }
+
+    /// Fetches and parses the generation forecast per production type. Times must be in UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, XML parsing fails, or time range is invalid.
+    pub async fn get_generation_forecast(
+        &self,
+        bidding_zone: BiddingZone,
+        process_type: ProcessType,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<GenerationForecastDocument> {
+        let xml = self
+            .fetch_generation_forecast(bidding_zone, process_type, period_start, period_end)
+            .await?;
+        parse_generation_forecast(&xml)
+    }
+
+    /// Fetches realised generation per production type as raw XML bytes. Times must be in UTC.
+    ///
+    /// `domain` is a [`BiddingZone`] or any other [`Domain`] the API aggregates generation for,
+    /// such as a control area or a whole region.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, URL construction fails, or time range is invalid.
+    pub async fn fetch_actual_generation(
+        &self,
+        domain: impl Into<Domain>,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<bytes::Bytes> {
+        validate_time_range(period_start, period_end)?;
+        let url = self.build_actual_generation_url(&domain.into(), period_start, period_end)?;
+        self.fetch_xml(url).await
+    }
+
+    /// Fetches and parses realised generation per production type. Times must be in UTC.
+    ///
+    /// Same domains as [`EntsoeClient::fetch_actual_generation`].
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, XML parsing fails, or time range is invalid.
+    pub async fn get_actual_generation(
+        &self,
+        domain: impl Into<Domain>,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<GenerationDocument> {
+        let xml = self
+            .fetch_actual_generation(domain, period_start, period_end)
+            .await?;
+        parse_actual_generation(&xml)
+    }
+
+    /// Fetches total load as raw XML bytes. Times must be in UTC.
+    ///
+    /// `process_type` selects the series: [`ProcessType::Realised`] for realised load,
+    /// [`ProcessType::DayAhead`] for the day-ahead forecast.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, URL construction fails, or time range is invalid.
+    pub async fn fetch_total_load(
+        &self,
+        bidding_zone: BiddingZone,
+        process_type: ProcessType,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<bytes::Bytes> {
+        validate_time_range(period_start, period_end)?;
+        let url =
+            self.build_total_load_url(bidding_zone, process_type, period_start, period_end)?;
+        self.fetch_xml(url).await
+    }
+
+    /// Fetches and parses total load. Times must be in UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, XML parsing fails, or time range is invalid.
+    pub async fn get_total_load(
+        &self,
+        bidding_zone: BiddingZone,
+        process_type: ProcessType,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<LoadDocument> {
+        let xml = self
+            .fetch_total_load(bidding_zone, process_type, period_start, period_end)
+            .await?;
+        parse_total_load(&xml)
+    }
+
+    /// Fetches installed generation capacity for a calendar year as raw XML bytes.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, URL construction fails, or the year is out of range.
+    pub async fn fetch_installed_capacity(
+        &self,
+        bidding_zone: BiddingZone,
+        year: i32,
+    ) -> Result<bytes::Bytes> {
+        let url = self.build_installed_capacity_url(bidding_zone, year)?;
+        self.fetch_xml(url).await
+    }
+
+    /// Fetches and parses installed generation capacity per production type for a calendar year.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, XML parsing fails, or the year is out of range.
+    pub async fn get_installed_capacity(
+        &self,
+        bidding_zone: BiddingZone,
+        year: i32,
+    ) -> Result<InstalledCapacityDocument> {
+        let xml = self.fetch_installed_capacity(bidding_zone, year).await?;
+        parse_installed_capacity(&xml, bidding_zone, year)
+    }
+
+    /// Fetches cross-border physical flows from `from` into `to` as raw XML bytes. Times must be in UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, URL construction fails, or time range is invalid.
+    pub async fn fetch_physical_flows(
+        &self,
+        from: BiddingZone,
+        to: BiddingZone,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<bytes::Bytes> {
+        validate_time_range(period_start, period_end)?;
+        let url = self.build_physical_flows_url(from, to, period_start, period_end)?;
+        self.fetch_xml(url).await
+    }
+
+    /// Fetches and parses cross-border physical flows from `from` into `to`. Times must be in UTC.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails, XML parsing fails, or time range is invalid.
+    pub async fn get_physical_flows(
+        &self,
+        from: BiddingZone,
+        to: BiddingZone,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<FlowDocument> {
+        let xml = self
+            .fetch_physical_flows(from, to, period_start, period_end)
+            .await?;
+        parse_physical_flows(&xml)
+    }
+
+    /// Sends `params` as query pairs to the API, with only the security token added, and returns
+    /// the raw body.
+    ///
+    /// An escape hatch for parameters the typed methods don't expose, such as `auction.Type`.
+    /// Nothing is validated, so `params` must include `documentType`, the domains and the period
+    /// the endpoint expects. Retries, redaction and error handling match the typed methods.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the HTTP request fails or URL construction fails.
+    pub async fn fetch_raw(&self, params: &[(&str, &str)]) -> Result<bytes::Bytes> {
+        let url = self.build_raw_url(params)?;
+        self.fetch_xml(url).await
+    }
+
+    async fn fetch_xml(&self, url: Url) -> Result<bytes::Bytes> {
+        let mut attempts = 1;
+        loop {
+            match self.fetch_xml_once(url.clone()).await {
+                Err(e) => {
+                    let delay = self.retry_delay(attempts, e)?;
+                    attempts += 1;
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn fetch_xml_once(&self, url: Url) -> Result<bytes::Bytes> {
+        let redacted_url = self.redact_url(&url);
+        #[cfg(feature = "tracing")]
+        tracing::debug!(url = %redacted_url, "sending request");
+        let (status, body) = self.transport.get(url).await.map_err(|e| match e {
+            EntsoeError::Http(e) => self.redact_http_error(e),
+            other => other,
+        })?;
+        #[cfg(feature = "tracing")]
+        tracing::debug!(
+            status = status.as_u16(),
+            bytes = body.len(),
+            "received response"
+        );
+
+        self.check_response(status, body, redacted_url)
+    }
+
+    /// Same document type as day-ahead prices, but for the intraday market agreement.
+    fn build_intraday_prices_url(
+        &self,
+        bidding_zone: BiddingZone,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<Url> {
+        self.build_url(
+            DocumentType::DayAheadPrices,
+            &[
+                ("contract_MarketAgreement.type", "A07"),
+                ("in_Domain", bidding_zone.eic_code()),
+                ("out_Domain", bidding_zone.eic_code()),
+            ],
+            period_start,
+            period_end,
+        )
+    }
+
+    fn build_generation_forecast_url(
+        &self,
+        bidding_zone: BiddingZone,
+        process_type: ProcessType,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<Url> {
+        self.build_url(
+            DocumentType::GenerationForecast,
+            &[
+                ("processType", process_type.code()),
+                ("in_Domain", bidding_zone.eic_code()),
+            ],
+            period_start,
+            period_end,
+        )
+    }
+
+    fn build_actual_generation_url(
+        &self,
+        domain: &Domain,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<Url> {
+        self.build_url(
+            DocumentType::ActualGeneration,
+            &[
+                ("processType", ProcessType::Realised.code()),
+                ("in_Domain", domain.eic_code()),
+            ],
+            period_start,
+            period_end,
+        )
+    }
+
+    fn build_total_load_url(
+        &self,
+        bidding_zone: BiddingZone,
+        process_type: ProcessType,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<Url> {
+        self.build_url(
+            DocumentType::TotalLoad,
+            &[
+                ("processType", process_type.code()),
+                ("outBiddingZone_Domain", bidding_zone.eic_code()),
+            ],
+            period_start,
+            period_end,
+        )
+    }
+
+    fn build_installed_capacity_url(&self, bidding_zone: BiddingZone, year: i32) -> Result<Url> {
+        let year_start = |year: i32| {
+            Utc.with_ymd_and_hms(year, 1, 1, 0, 0, 0)
+                .single()
+                .ok_or_else(|| EntsoeError::InvalidTimeRange(format!("invalid year: {year}")))
+        };
+
+        self.build_url(
+            DocumentType::InstalledCapacity,
+            &[
+                ("processType", ProcessType::YearAhead.code()),
+                ("in_Domain", bidding_zone.eic_code()),
+            ],
+            year_start(year)?,
+            year_start(year + 1)?,
+        )
+    }
+
+    /// Flows go out of `from` and into `to`, so the two domains differ.
+    fn build_physical_flows_url(
+        &self,
+        from: BiddingZone,
+        to: BiddingZone,
+        period_start: DateTime<Utc>,
+        period_end: DateTime<Utc>,
+    ) -> Result<Url> {
+        self.build_url(
+            DocumentType::PhysicalFlows,
+            &[
+                ("in_Domain", to.eic_code()),
+                ("out_Domain", from.eic_code()),
+            ],
+            period_start,
+            period_end,
+        )
+    }
+}
+
+/// UTC range from local midnight of `day` in `tz` to local midnight of the following day.
+///
+/// Daylight saving transitions make the range 23 or 25 hours long. Where midnight falls twice,
+/// the earlier instant is used.
+///
+/// # Errors
+///
+/// Returns [`EntsoeError::InvalidTimeRange`] if midnight is skipped in `tz` on either day.
+pub fn local_day_range(day: NaiveDate, tz: Tz) -> Result<(DateTime<Utc>, DateTime<Utc>)> {
+    let midnight = |date: NaiveDate| {
+        tz.from_local_datetime(&date.and_time(NaiveTime::MIN))
+            .earliest()
+            .map(|t| t.with_timezone(&Utc))
+            .ok_or_else(|| {
+                EntsoeError::InvalidTimeRange(format!("midnight of {date} does not exist in {tz}"))
+            })
+    };
+    let next_day = day
+        .succ_opt()
+        .ok_or_else(|| EntsoeError::InvalidTimeRange(format!("no day after {day}")))?;
+    Ok((midnight(day)?, midnight(next_day)?))
+}
+
+fn validate_time_range(period_start: DateTime<Utc>, period_end: DateTime<Utc>) -> Result<()> {
+    validate_period_order(period_start, period_end)?;
+    if period_end - period_start > Duration::days(MAX_QUERY_DAYS) {
+        return Err(EntsoeError::InvalidTimeRange(format!(
+            "range of {} days exceeds the API limit of {MAX_QUERY_DAYS} days",
+            (period_end - period_start).num_days()
+        )));
+    }
+    Ok(())
+}
+
+fn validate_period_order(period_start: DateTime<Utc>, period_end: DateTime<Utc>) -> Result<()> {
+    if period_start >= period_end {
+        return Err(EntsoeError::InvalidTimeRange(
+            "period_start must be before period_end".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Warns, and returns `true`, if day-ahead data cannot exist yet for the end of the range.
+pub(crate) fn warn_if_beyond_day_ahead_horizon(
+    period_end: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> bool {
+    let beyond = period_end > now + Duration::hours(DAY_AHEAD_HORIZON_HOURS);
+    #[cfg(feature = "tracing")]
+    if beyond {
+        tracing::warn!(
+            %period_end,
+            "period_end is more than {DAY_AHEAD_HORIZON_HOURS} hours ahead; day-ahead prices are not published that far out"
+        );
+    }
+    beyond
+}
+
+fn redact_query_token(url: &Url) -> Url {
+    let mut redacted = url.clone();
+    redacted
+        .query_pairs_mut()
+        .clear()
+        .extend_pairs(url.query_pairs().map(|(k, v)| {
+            if k == "securityToken" {
+                (k, "***".into())
+            } else {
+                (k, v)
+            }
+        }));
+    redacted
+}
+
+/// Key under which a day-ahead price document is cached.
+pub(crate) fn day_ahead_cache_key(
+    bidding_zone: BiddingZone,
+    period_start: DateTime<Utc>,
+    period_end: DateTime<Utc>,
+) -> CacheKey {
+    (
+        bidding_zone.eic_code(),
+        format_timestamp(period_start),
+        format_timestamp(period_end),
+        DocumentType::DayAheadPrices.code(),
+    )
+}
+
+fn format_timestamp(dt: DateTime<Utc>) -> String {
+    dt.format("%Y%m%d%H%M").to_string()
+}
+
+/// Whether a response body is an HTML page rather than an XML document. The transport only hands
+/// over the body, so this sniffs for a leading `<!DOCTYPE html` or `<html` instead of checking
+/// `Content-Type`.
+fn is_html(body: &[u8]) -> bool {
+    let body = body.trim_ascii_start();
+    [b"<!doctype html".as_slice(), b"<html"]
+        .iter()
+        .any(|prefix| {
+            body.get(..prefix.len())
+                .is_some_and(|head| head.eq_ignore_ascii_case(prefix))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_format_timestamp() {
+        let dt = Utc.with_ymd_and_hms(2024, 1, 15, 14, 30, 0).unwrap();
+        assert_eq!(format_timestamp(dt), "202401151430");
+    }
+
+    #[test]
+    fn test_build_url() {
+        let client = EntsoeClient::new("test-token");
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 16, 0, 0, 0).unwrap();
+
+        let url = client
+            .day_ahead_prices_url(BiddingZone::DE, start, end)
+            .unwrap();
+
+        let url_str = url.as_str();
+        assert!(url_str.contains("documentType=A44"));
+        assert!(url_str.contains("in_Domain=10Y1001A1001A82H"));
+        assert!(url_str.contains("out_Domain=10Y1001A1001A82H"));
+        assert!(url_str.contains("periodStart=202401150000"));
+        assert!(url_str.contains("periodEnd=202401160000"));
+        assert!(url_str.contains("securityToken=test-token"));
+    }
+
+    #[test]
+    fn test_day_ahead_prices_url_is_exact_request_url() {
+        let client = EntsoeClient::new("test-token");
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 16, 0, 0, 0).unwrap();
+
+        let url = client
+            .day_ahead_prices_url(BiddingZone::FI, start, end)
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://web-api.tp.entsoe.eu/api?documentType=A44\
+             &in_Domain=10YFI-1--------U&out_Domain=10YFI-1--------U\
+             &periodStart=202401150000&periodEnd=202401160000&securityToken=test-token"
+        );
+        assert!(matches!(
+            client.day_ahead_prices_url(BiddingZone::FI, end, start),
+            Err(EntsoeError::InvalidTimeRange(_))
+        ));
+    }
+
+    #[test]
+    fn test_build_intraday_prices_url() {
+        let client = EntsoeClient::new("test-token");
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 16, 0, 0, 0).unwrap();
+
+        let url = client
+            .build_intraday_prices_url(BiddingZone::DE, start, end)
+            .unwrap();
+
+        let url_str = url.as_str();
+        assert!(url_str.contains("documentType=A44"));
+        assert!(url_str.contains("contract_MarketAgreement.type=A07"));
+        assert!(url_str.contains("in_Domain=10Y1001A1001A82H"));
+        assert!(url_str.contains("out_Domain=10Y1001A1001A82H"));
+
+        let day_ahead = client
+            .day_ahead_prices_url(BiddingZone::DE, start, end)
+            .unwrap();
+        assert!(!day_ahead.as_str().contains("contract_MarketAgreement.type"));
+    }
+
+    #[tokio::test]
+    async fn test_get_intraday_prices() {
+        let mut server = mockito::Server::new_async().await;
+        let xml = include_str!("../tests/fixtures/day_ahead_prices_fi.xml");
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "contract_MarketAgreement.type".to_string(),
+                "A07".to_string(),
+            ))
+            .with_body(xml)
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let doc = client
+            .get_intraday_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(doc, parse_day_ahead_prices(xml.as_bytes()).unwrap());
+    }
+
+    #[test]
+    fn test_build_generation_forecast_url() {
+        let client = EntsoeClient::new("test-token");
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 16, 0, 0, 0).unwrap();
+
+        let url = client
+            .build_generation_forecast_url(BiddingZone::FI, ProcessType::DayAhead, start, end)
+            .unwrap();
+
+        let url_str = url.as_str();
+        assert!(url_str.contains("documentType=A71"));
+        assert!(url_str.contains("processType=A01"));
+        assert!(url_str.contains("in_Domain=10YFI-1--------U"));
+        assert!(!url_str.contains("out_Domain"));
+        assert!(url_str.contains("periodStart=202401150000"));
+        assert!(url_str.contains("securityToken=test-token"));
+
+        for (process_type, code) in [
+            (ProcessType::DayAhead, "processType=A01"),
+            (ProcessType::Intraday, "processType=A40"),
+            (ProcessType::Current, "processType=A18"),
+        ] {
+            let url = client
+                .build_generation_forecast_url(BiddingZone::FI, process_type, start, end)
+                .unwrap();
+            assert!(url.as_str().contains(code), "{url}");
+        }
+    }
+
+    #[test]
+    fn test_build_actual_generation_url() {
+        let client = EntsoeClient::new("test-token");
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 16, 0, 0, 0).unwrap();
+
+        let url = client
+            .build_actual_generation_url(&BiddingZone::FI.into(), start, end)
+            .unwrap();
+
+        let url_str = url.as_str();
+        assert!(url_str.contains("documentType=A75"));
+        assert!(url_str.contains("processType=A16"));
+        assert!(url_str.contains("in_Domain=10YFI-1--------U"));
+        assert!(url_str.contains("periodEnd=202401160000"));
+    }
+
+    #[test]
+    fn test_build_actual_generation_url_for_region() {
+        let client = EntsoeClient::new("test-token");
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 16, 0, 0, 0).unwrap();
+        let nordic = Domain::from_eic("10Y1001A1001A91G").unwrap();
+
+        let url = client
+            .build_actual_generation_url(&nordic, start, end)
+            .unwrap();
+
+        assert_eq!(
+            url.as_str(),
+            "https://web-api.tp.entsoe.eu/api?documentType=A75&processType=A16\
+             &in_Domain=10Y1001A1001A91G&periodStart=202401150000&periodEnd=202401160000\
+             &securityToken=test-token"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_actual_generation() {
+        let mut server = mockito::Server::new_async().await;
+        let xml = include_str!("../tests/fixtures/actual_generation_fi.xml");
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "documentType".to_string(),
+                "A75".to_string(),
+            ))
+            .with_body(xml)
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 13, 0, 0, 0).unwrap();
+
+        let doc = client
+            .get_actual_generation(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(doc.by_type.len(), 3);
+        assert_eq!(doc.by_type[&crate::PsrType::WindOnshore].len(), 8);
+    }
+
+    #[test]
+    fn test_build_total_load_url() {
+        let client = EntsoeClient::new("test-token");
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 16, 0, 0, 0).unwrap();
+
+        let url = client
+            .build_total_load_url(BiddingZone::FI, ProcessType::Realised, start, end)
+            .unwrap();
+
+        let url_str = url.as_str();
+        assert!(url_str.contains("documentType=A65"));
+        assert!(url_str.contains("processType=A16"));
+        assert!(url_str.contains("outBiddingZone_Domain=10YFI-1--------U"));
+        assert!(url_str.contains("periodEnd=202401160000"));
+
+        let url = client
+            .build_total_load_url(BiddingZone::FI, ProcessType::DayAhead, start, end)
+            .unwrap();
+        assert!(url.as_str().contains("processType=A01"));
+    }
+
+    #[test]
+    fn test_build_installed_capacity_url() {
+        let client = EntsoeClient::new("test-token");
+
+        let url = client
+            .build_installed_capacity_url(BiddingZone::FI, 2025)
+            .unwrap();
+
+        let url_str = url.as_str();
+        assert!(url_str.contains("documentType=A68"));
+        assert!(url_str.contains("processType=A33"));
+        assert!(url_str.contains("in_Domain=10YFI-1--------U"));
+        assert!(url_str.contains("periodStart=202501010000"));
+        assert!(url_str.contains("periodEnd=202601010000"));
+    }
+
+    #[test]
+    fn test_build_physical_flows_url() {
+        let client = EntsoeClient::new("test-token");
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2024, 1, 16, 0, 0, 0).unwrap();
+
+        let url = client
+            .build_physical_flows_url(BiddingZone::FI, BiddingZone::SE1, start, end)
+            .unwrap();
+
+        let url_str = url.as_str();
+        assert!(url_str.contains("documentType=A11"));
+        assert!(url_str.contains("out_Domain=10YFI-1--------U"));
+        assert!(url_str.contains("in_Domain=10Y1001A1001A44P"));
+        assert!(url_str.contains("securityToken=test-token"));
+    }
+
+    #[tokio::test]
+    async fn test_accept_header_sent() {
+        let mut server = mockito::Server::new_async().await;
+        let xml = include_str!("../tests/fixtures/day_ahead_prices_fi.xml");
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .match_header("accept", "application/xml")
+            .with_body(xml)
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let doc = client
+            .get_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert!(!doc.prices.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_accept_header_override() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .match_header("accept", "text/xml")
+            .with_body("<root/>")
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .accept("text/xml")
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        client
+            .fetch_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_user_agent_and_default_header_sent() {
+        let mut server = mockito::Server::new_async().await;
+        let xml = include_str!("../tests/fixtures/day_ahead_prices_fi.xml");
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .match_header("user-agent", "acme-billing/2.1 (ops@example.com)")
+            .match_header("x-request-source", "nightly")
+            .with_body(xml)
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .user_agent("acme-billing/2.1 (ops@example.com)")
+            .default_header("X-Request-Source", "nightly")
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        client
+            .get_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_api_token_from_file() {
+        let path = std::env::temp_dir().join(format!("entsoe-token-{}", std::process::id()));
+        std::fs::write(&path, "file-token\n").unwrap();
+
+        let mut server = mockito::Server::new_async().await;
+        let xml = include_str!("../tests/fixtures/day_ahead_prices_fi.xml");
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "securityToken".into(),
+                "file-token".into(),
+            ))
+            .with_body(xml)
+            .create_async()
+            .await;
+
+        let builder = EntsoeClient::builder("unused").api_token_from_file(&path);
+        std::fs::write(&path, " \n").unwrap();
+        let empty = EntsoeClient::builder("unused").api_token_from_file(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        let client = builder
+            .unwrap()
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+        client
+            .get_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+        mock.assert_async().await;
+
+        let err = empty.err().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert!(
+            EntsoeClient::builder("unused")
+                .api_token_from_file(&path)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_builds_with_proxy() {
+        let proxy = reqwest::Proxy::https("http://proxy.example.com:3128")
+            .unwrap()
+            .no_proxy(reqwest::NoProxy::from_string("localhost"));
+
+        let client = EntsoeClient::builder("test-token").proxy(proxy).build();
+
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn test_invalid_default_header() {
+        let result = EntsoeClient::builder("test-token")
+            .default_header("bad header", "value")
+            .build();
+        assert!(matches!(result, Err(EntsoeError::InvalidHeader(_))));
+    }
+
+    #[tokio::test]
+    async fn test_range_merges_overlapping_chunks() {
+        let mut server = mockito::Server::new_async().await;
+        let xml = include_str!("../tests/fixtures/day_ahead_prices_fi.xml");
+        let mut mocks = Vec::new();
+        for period_start in ["202510112200", "202510122200"] {
+            mocks.push(
+                server
+                    .mock("GET", "/api")
+                    .match_query(mockito::Matcher::UrlEncoded(
+                        "periodStart".into(),
+                        period_start.into(),
+                    ))
+                    .with_body(xml)
+                    .create_async()
+                    .await,
+            );
+        }
+
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 13, 22, 0, 0).unwrap();
+
+        // Both chunks return the full two-day fixture, so every point is duplicated
+        let doc = client
+            .get_day_ahead_prices_chunked(BiddingZone::FI, start, end, Duration::days(1))
+            .await
+            .unwrap();
+
+        for mock in mocks {
+            mock.assert_async().await;
+        }
+        let single = parse_day_ahead_prices(xml.as_bytes()).unwrap();
+        assert_eq!(doc.prices.len(), single.prices.len());
+        assert!(
+            doc.prices
+                .windows(2)
+                .all(|w| w[0].timestamp < w[1].timestamp)
+        );
+        assert_eq!(doc.period_start, start);
+        assert_eq!(doc.period_end, end);
+    }
+
+    #[tokio::test]
+    async fn test_cache_hit_skips_request() {
+        let mut server = mockito::Server::new_async().await;
+        let xml = include_str!("../tests/fixtures/day_ahead_prices_fi.xml");
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_body(xml)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .cache(std::time::Duration::from_mins(1))
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let first = client
+            .get_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+        let second = client
+            .get_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(first, second);
+    }
+
+    #[tokio::test]
+    async fn test_cache_expiry_and_clear_refetch() {
+        let mut server = mockito::Server::new_async().await;
+        let xml = include_str!("../tests/fixtures/day_ahead_prices_fi.xml");
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_body(xml)
+            .expect(3)
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .cache(std::time::Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        client
+            .get_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        client
+            .get_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+        client.clear_cache();
+        client
+            .get_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_token_redacted_from_api_error() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_status(401)
+            .with_body("Unknown token secret-token")
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("secret-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let err = client
+            .fetch_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap_err();
+
+        let message = err.to_string();
+        assert!(!message.contains("secret-token"), "{message}");
+        assert!(message.contains("***"));
+    }
+
+    #[tokio::test]
+    async fn test_percent_encoded_token_redacted_from_url() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_status(429)
+            .create_async()
+            .await;
+
+        // Encoded as a%2Bb%2Fc%3D in the query string
+        let client = EntsoeClient::builder("a+b/c=")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let err = client
+            .fetch_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap_err();
+
+        match err {
+            EntsoeError::RateLimited { url } => {
+                assert!(url.contains("securityToken=***"), "{url}");
+                assert!(!url.contains("a%2Bb%2Fc%3D"), "{url}");
+            }
+            other => panic!("Expected RateLimited, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_too_many_requests_is_rate_limited() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_status(429)
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("secret-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let err = client
+            .fetch_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, EntsoeError::RateLimited { .. }), "{err:?}");
+        assert!(err.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_retries_exhausted_wraps_last_error() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .with_body("Service Unavailable")
+            .expect(3)
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .retry(3, std::time::Duration::ZERO)
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let err = client
+            .fetch_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap_err();
+
+        mock.assert_async().await;
+        match err {
+            EntsoeError::RetriesExhausted { attempts, last } => {
+                assert_eq!(attempts, 3);
+                assert!(matches!(*last, EntsoeError::ApiError { status: 503, .. }));
+            }
+            other => panic!("Expected RetriesExhausted, got {other:?}"),
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_tracing_events_on_service_unavailable() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .retry(2, std::time::Duration::ZERO)
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let _ = client
+            .fetch_day_ahead_prices(BiddingZone::FI, start, end)
+            .await;
+
+        assert!(logs_contain("fetch_day_ahead_prices"));
+        assert!(logs_contain("zone=\"FI\""));
+        assert!(logs_contain("status=503"));
+        assert!(logs_contain("retrying request"));
+        assert!(logs_contain("securityToken=***"));
+        assert!(!logs_contain("test-token"));
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_error_is_not_retried() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_status(400)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .retry(3, std::time::Duration::ZERO)
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let err = client
+            .fetch_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap_err();
+
+        mock.assert_async().await;
+        assert!(matches!(err, EntsoeError::ApiError { status: 400, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_api_error_carries_status_and_url() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_status(503)
+            .with_body("Service Unavailable")
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("secret-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let err = client
+            .fetch_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap_err();
+
+        match err {
+            EntsoeError::ApiError { status, url, body } => {
+                assert_eq!(status, 503);
+                let url = Url::parse(&url).unwrap();
+                assert_eq!(url.path(), "/api");
+                assert!(url.as_str().contains("documentType=A44"));
+                assert!(url.as_str().contains("securityToken=***"));
+                assert_eq!(body, "Service Unavailable");
+            }
+            other => panic!("Expected ApiError, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_html_401_is_unauthorized() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_status(401)
+            .with_header("content-type", "text/html")
+            .with_body(
+                "\n<!DOCTYPE html>\n<html><head><title>401 Unauthorized</title></head>\
+                 <body><h1>Unauthorized</h1><p>Lots of markup here</p></body></html>",
+            )
+            .create_async()
+            .await;
+
+        let client = EntsoeClient::builder("secret-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let err = client
+            .fetch_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap_err();
+
+        match &err {
+            EntsoeError::Unauthorized { status, url } => {
+                assert_eq!(*status, 401);
+                assert!(url.contains("securityToken=***"));
+            }
+            other => panic!("Expected Unauthorized, got {other:?}"),
+        }
+        assert!(!err.to_string().contains("<html"), "{err}");
+    }
+
+    #[test]
+    fn test_is_html() {
+        assert!(is_html(b"<!DOCTYPE html><html></html>"));
+        assert!(is_html(b"  <HTML lang=\"en\">"));
+        assert!(!is_html(
+            b"<?xml version=\"1.0\"?><Acknowledgement_MarketDocument/>"
+        ));
+        assert!(!is_html(b"Unauthorized"));
+        assert!(!is_html(b""));
+    }
+
+    #[tokio::test]
+    async fn test_token_redacted_from_http_error() {
+        // Nothing listens on port 1, so the request fails with the URL in the error
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        let client = EntsoeClient::builder("secret-token")
+            .base_url("http://127.0.0.1:1/api")
+            .build()
+            .unwrap();
+        let err = client
+            .fetch_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap_err();
+        let message = format!("{err} {err:?}");
+        assert!(!message.contains("secret-token"), "{message}");
+
+        let client = EntsoeClient::builder("secret-token")
+            .base_url("http://127.0.0.1:1/api")
+            .redact_token_in_errors(false)
+            .build()
+            .unwrap();
+        let err = client
+            .fetch_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("secret-token"));
+    }
+
+    struct FixtureTransport;
+
+    /// Answers FI immediately and every other zone only after a long delay.
+    struct SlowTransport;
+
+    impl HttpTransport for SlowTransport {
+        async fn get(&self, url: Url) -> Result<(reqwest::StatusCode, bytes::Bytes)> {
+            if !url.as_str().contains(BiddingZone::FI.eic_code()) {
+                tokio::time::sleep(std::time::Duration::from_secs(5)).await;
+            }
+            let xml = include_bytes!("../tests/fixtures/day_ahead_prices_fi.xml");
+            Ok((reqwest::StatusCode::OK, bytes::Bytes::from_static(xml)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multi_deadline_returns_partial_results() {
+        let client = EntsoeClient::with_transport("test-token", SlowTransport);
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_millis(200);
+
+        let results = client
+            .get_day_ahead_prices_multi_deadline(
+                &[BiddingZone::FI, BiddingZone::SE1, BiddingZone::SE2],
+                start,
+                end,
+                deadline,
+            )
+            .await;
+
+        let zones: Vec<BiddingZone> = results.iter().map(|(zone, _)| *zone).collect();
+        assert_eq!(zones, [BiddingZone::FI, BiddingZone::SE1, BiddingZone::SE2]);
+        assert!(results[0].1.is_ok());
+        assert!(matches!(results[1].1, Err(EntsoeError::Timeout)));
+        assert!(matches!(results[2].1, Err(EntsoeError::Timeout)));
+    }
+
+    /// Serves a document without price ranges first and one with them on every later call.
+    #[derive(Default)]
+    struct PublishingTransport {
+        calls: std::sync::atomic::AtomicUsize,
+    }
+
+    impl HttpTransport for PublishingTransport {
+        async fn get(&self, _url: Url) -> Result<(reqwest::StatusCode, bytes::Bytes)> {
+            let xml: &'static [u8] =
+                if self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                    include_bytes!("../tests/fixtures/day_ahead_prices_fi.xml")
+                } else {
+                    include_bytes!("../tests/fixtures/day_ahead_prices_price_range.xml")
+                };
+            Ok((reqwest::StatusCode::OK, bytes::Bytes::from_static(xml)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_resolves_on_second_poll() {
+        let client = EntsoeClient::with_transport("test-token", PublishingTransport::default());
+
+        let doc = client
+            .wait_for(
+                BiddingZone::FI,
+                |doc| doc.max_price_range.is_some(),
+                std::time::Duration::from_millis(10),
+                std::time::Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+
+        assert!(doc.max_price_range.is_some());
+        assert_eq!(
+            client
+                .transport
+                .calls
+                .load(std::sync::atomic::Ordering::SeqCst),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_times_out() {
+        let client = EntsoeClient::with_transport("test-token", FixtureTransport);
+
+        let result = client
+            .wait_for(
+                BiddingZone::FI,
+                |_| false,
+                std::time::Duration::from_millis(10),
+                std::time::Duration::from_millis(50),
+            )
+            .await;
+
+        assert!(matches!(result, Err(EntsoeError::Timeout)));
+    }
+
+    impl HttpTransport for FixtureTransport {
+        async fn get(&self, url: Url) -> Result<(reqwest::StatusCode, bytes::Bytes)> {
+            assert!(url.as_str().contains("documentType=A44"));
+            let xml = include_bytes!("../tests/fixtures/day_ahead_prices_fi.xml");
+            Ok((reqwest::StatusCode::OK, bytes::Bytes::from_static(xml)))
+        }
+    }
+
+    /// Records each request's period and answers with one hourly point at its start.
+    #[derive(Default)]
+    struct RecordingTransport {
+        periods: std::sync::Mutex<Vec<(String, String)>>,
+    }
+
+    impl HttpTransport for RecordingTransport {
+        async fn get(&self, url: Url) -> Result<(reqwest::StatusCode, bytes::Bytes)> {
+            let param = |name: &str| {
+                url.query_pairs()
+                    .find(|(k, _)| k == name)
+                    .map(|(_, v)| v.into_owned())
+                    .unwrap()
+            };
+            let (start, end) = (param("periodStart"), param("periodEnd"));
+            let timestamp = |s: &str| {
+                chrono::NaiveDateTime::parse_from_str(s, "%Y%m%d%H%M")
+                    .unwrap()
+                    .format("%Y-%m-%dT%H:%MZ")
+                    .to_string()
+            };
+            let interval = format!(
+                "<start>{}</start><end>{}</end>",
+                timestamp(&start),
+                timestamp(&end)
+            );
+            let xml = format!(
+                "<Publication_MarketDocument><period.timeInterval>{interval}</period.timeInterval>\
+                 <TimeSeries><currency_Unit.name>EUR</currency_Unit.name><Period>\
+                 <timeInterval>{interval}</timeInterval><resolution>PT60M</resolution>\
+                 <Point><position>1</position><price.amount>10</price.amount></Point>\
+                 </Period></TimeSeries></Publication_MarketDocument>"
+            );
+            self.periods.lock().unwrap().push((start, end));
+            Ok((reqwest::StatusCode::OK, bytes::Bytes::from(xml)))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_range_longer_than_api_limit() {
+        let client = EntsoeClient::with_transport("test-token", RecordingTransport::default());
+        let start = Utc.with_ymd_and_hms(2023, 12, 31, 23, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 6, 30, 22, 0, 0).unwrap();
+
+        let doc = client
+            .get_day_ahead_prices_range(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+
+        let chunk_end = start + Duration::days(MAX_PRICE_QUERY_DAYS);
+        let periods = client.transport.periods.lock().unwrap().clone();
+        assert_eq!(
+            periods,
+            [
+                (format_timestamp(start), format_timestamp(chunk_end)),
+                (format_timestamp(chunk_end), format_timestamp(end)),
+            ]
+        );
+        let timestamps: Vec<_> = doc.prices.iter().map(|p| p.timestamp).collect();
+        assert_eq!(timestamps, [start, chunk_end]);
+        assert_eq!(doc.period_end, end);
+    }
+
+    #[tokio::test]
+    async fn test_custom_transport() {
+        let client = EntsoeClient::with_transport("test-token", FixtureTransport);
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 13, 22, 0, 0).unwrap();
+
+        let doc = client
+            .get_day_ahead_prices(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+
+        assert_eq!(doc.currency, "EUR");
+        assert_eq!(doc.period_start, start);
+        assert_eq!(doc.period_end, end);
+        assert!(!doc.prices.is_empty());
+    }
+
+    #[test]
+    fn test_local_day_range_dst() {
+        let helsinki = chrono_tz::Europe::Helsinki;
+        let range = |y, m, d| local_day_range(NaiveDate::from_ymd_opt(y, m, d).unwrap(), helsinki);
+
+        // Winter: UTC+2 all day
+        let (start, end) = range(2025, 1, 15).unwrap();
+        assert_eq!(start, Utc.with_ymd_and_hms(2025, 1, 14, 22, 0, 0).unwrap());
+        assert_eq!(end - start, Duration::hours(24));
+
+        // Clocks spring forward on 30 March 2025: a 23-hour day
+        let (start, end) = range(2025, 3, 30).unwrap();
+        assert_eq!(start, Utc.with_ymd_and_hms(2025, 3, 29, 22, 0, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2025, 3, 30, 21, 0, 0).unwrap());
+
+        // And fall back on 26 October 2025: a 25-hour day
+        let (start, end) = range(2025, 10, 26).unwrap();
+        assert_eq!(start, Utc.with_ymd_and_hms(2025, 10, 25, 21, 0, 0).unwrap());
+        assert_eq!(end, Utc.with_ymd_and_hms(2025, 10, 26, 22, 0, 0).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_get_day_ahead_prices_for_local_day() {
+        let mut server = mockito::Server::new_async().await;
+        let xml = include_str!("../tests/fixtures/day_ahead_prices_fi.xml");
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("periodStart".into(), "202503292200".into()),
+                mockito::Matcher::UrlEncoded("periodEnd".into(), "202503302100".into()),
+            ]))
+            .with_body(xml)
+            .create_async()
+            .await;
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+
+        client
+            .get_day_ahead_prices_for_local_day(
+                BiddingZone::FI,
+                NaiveDate::from_ymd_opt(2025, 3, 30).unwrap(),
+                chrono_tz::Europe::Helsinki,
+            )
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+    }
+
+    /// Hourly FI prices from `start`, in the shape of `day_ahead_prices_compact.xml`.
+    fn hourly_prices_xml(start: DateTime<Utc>, prices: &[f64]) -> String {
+        use std::fmt::Write;
+
+        let end = start + Duration::hours(i64::try_from(prices.len()).unwrap());
+        let interval = format!(
+            "<start>{}</start><end>{}</end>",
+            start.format("%Y-%m-%dT%H:%MZ"),
+            end.format("%Y-%m-%dT%H:%MZ")
+        );
+        let mut points = String::new();
+        for (i, price) in prices.iter().enumerate() {
+            write!(
+                points,
+                "<Point><position>{}</position><price.amount>{price}</price.amount></Point>",
+                i + 1
+            )
+            .unwrap();
+        }
+        format!(
+            "<Publication_MarketDocument><type>A44</type>\
+             <period.timeInterval>{interval}</period.timeInterval><TimeSeries>\
+             <currency_Unit.name>EUR</currency_Unit.name><curveType>A01</curveType>\
+             <Period><timeInterval>{interval}</timeInterval><resolution>PT60M</resolution>\
+             {points}</Period></TimeSeries></Publication_MarketDocument>"
+        )
+    }
+
+    #[tokio::test]
+    async fn test_range_continues_after_truncated_response() {
+        let start = Utc.with_ymd_and_hms(2025, 9, 30, 22, 0, 0).unwrap();
+        let midnight = Utc.with_ymd_and_hms(2025, 10, 1, 0, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 1, 2, 0, 0).unwrap();
+
+        let mut server = mockito::Server::new_async().await;
+        let truncated = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("periodStart".into(), "202509302200".into()),
+                mockito::Matcher::UrlEncoded("periodEnd".into(), "202510010200".into()),
+            ]))
+            .with_body(hourly_prices_xml(start, &[10.0, 11.0]))
+            .expect(1)
+            .create_async()
+            .await;
+        let remainder = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("periodStart".into(), "202510010000".into()),
+                mockito::Matcher::UrlEncoded("periodEnd".into(), "202510010200".into()),
+            ]))
+            .with_body(hourly_prices_xml(midnight, &[12.0, 13.0]))
+            .expect(1)
+            .create_async()
+            .await;
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+
+        let doc = client
+            .get_day_ahead_prices_range(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+
+        truncated.assert_async().await;
+        remainder.assert_async().await;
+        let timestamps: Vec<_> = doc.prices.iter().map(|p| p.timestamp).collect();
+        assert_eq!(
+            timestamps,
+            [0, 1, 2, 3].map(|h| start + Duration::hours(h)).to_vec()
+        );
+        assert_eq!(doc.period_end, end);
+    }
+
+    #[tokio::test]
+    async fn test_range_does_not_refetch_compressed_response() {
+        let start = Utc.with_ymd_and_hms(2025, 9, 30, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 1, 2, 0, 0).unwrap();
+
+        // Covers the whole range, but A03 leaves out the repeated price at position 4
+        let compressed = hourly_prices_xml(start, &[10.0, 11.0, 12.0, 12.0])
+            .replace("<curveType>A01</curveType>", "<curveType>A03</curveType>")
+            .replace(
+                "<Point><position>4</position><price.amount>12</price.amount></Point>",
+                "",
+            );
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_body(compressed)
+            .expect(1)
+            .create_async()
+            .await;
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+
+        let doc = client
+            .get_day_ahead_prices_range(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(doc.prices.len(), 3);
+        assert_eq!(doc.period_end, end);
+    }
+
+    #[tokio::test]
+    async fn test_range_stops_when_remainder_has_no_data() {
+        let start = Utc.with_ymd_and_hms(2025, 9, 30, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 1, 2, 0, 0).unwrap();
+
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "periodStart".into(),
+                "202509302200".into(),
+            ))
+            .with_body(hourly_prices_xml(start, &[10.0, 11.0]))
+            .create_async()
+            .await;
+        let remainder = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::UrlEncoded(
+                "periodStart".into(),
+                "202510010000".into(),
+            ))
+            .with_body(
+                "<Acknowledgement_MarketDocument><Reason><code>999</code>\
+                 <text>No matching data found</text></Reason></Acknowledgement_MarketDocument>",
+            )
+            .expect(1)
+            .create_async()
+            .await;
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+
+        let doc = client
+            .get_day_ahead_prices_range(BiddingZone::FI, start, end)
+            .await
+            .unwrap();
+
+        remainder.assert_async().await;
+        assert_eq!(doc.prices.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_raw_appends_params_and_token() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("documentType".into(), "A44".into()),
+                mockito::Matcher::UrlEncoded("auction.Type".into(), "A01".into()),
+                mockito::Matcher::UrlEncoded(
+                    "classificationSequence_AttributeInstanceComponent.Position".into(),
+                    "1".into(),
+                ),
+                mockito::Matcher::UrlEncoded("securityToken".into(), "test-token".into()),
+            ]))
+            .with_body("<raw/>")
+            .create_async()
+            .await;
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+
+        let body = client
+            .fetch_raw(&[
+                ("documentType", "A44"),
+                ("auction.Type", "A01"),
+                (
+                    "classificationSequence_AttributeInstanceComponent.Position",
+                    "1",
+                ),
+            ])
+            .await
+            .unwrap();
+
+        mock.assert_async().await;
+        assert_eq!(&body[..], b"<raw/>");
+    }
+
+    #[test]
+    fn test_validate_time_range_limits() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+
+        assert!(validate_time_range(start, start + Duration::hours(24)).is_ok());
+        assert!(validate_time_range(start, start + Duration::days(372)).is_ok());
+
+        match validate_time_range(start, start + Duration::days(400)) {
+            Err(EntsoeError::InvalidTimeRange(message)) => {
+                assert!(message.contains("400 days"), "{message}");
+                assert!(message.contains("372"), "{message}");
+            }
+            other => panic!("Expected InvalidTimeRange, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_day_ahead_horizon() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        assert!(!warn_if_beyond_day_ahead_horizon(
+            now + Duration::hours(36),
+            now
+        ));
+        assert!(warn_if_beyond_day_ahead_horizon(
+            now + Duration::hours(48),
+            now
+        ));
     }
 
     #[test]
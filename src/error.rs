@@ -14,14 +14,222 @@ pub enum EntsoeError {
     #[error("Invalid time range: {0}")]
     InvalidTimeRange(String),
 
-    #[error("API error: {0}")]
-    ApiError(String),
+    /// A resolution conversion was requested that is not supported, e.g. upsampling.
+    #[error("Invalid resolution: {0}")]
+    InvalidResolution(String),
+
+    /// A header configured on the client builder has an invalid name or value.
+    #[error("Invalid HTTP header: {0}")]
+    InvalidHeader(String),
+
+    /// The API answered with a non-2xx status. `url` has the security token redacted unless
+    /// redaction was disabled on the client.
+    #[error("API returned status {status} for {url}: {body}")]
+    ApiError {
+        status: u16,
+        url: String,
+        body: String,
+    },
+
+    /// The API answered with 401 or 403 and an HTML error page, which is what ENTSO-E serves
+    /// for a missing or invalid security token.
+    #[error("API rejected the security token (status {status}) for {url}")]
+    Unauthorized { status: u16, url: String },
+
+    /// The API answered with HTTP 429 Too Many Requests.
+    #[error("API rate limit exceeded for {url}")]
+    RateLimited { url: String },
+
+    /// The API answered with an `Acknowledgement_MarketDocument` instead of data, e.g.
+    /// "No matching data found".
+    #[error("API returned error (code {code}): {text}")]
+    Acknowledgement { code: String, text: String },
+
+    /// A deadline passed before the request completed or was started.
+    #[error("Deadline passed before the request completed")]
+    Timeout,
+
+    /// A retryable error persisted through every attempt the client was configured to make.
+    #[error("Giving up after {attempts} attempts: {last}")]
+    RetriesExhausted {
+        attempts: u32,
+        last: Box<EntsoeError>,
+    },
 
     #[error("Failed to parse XML: {0}")]
     XmlParseError(String),
 
     #[error("Missing required field: {0}")]
     MissingField(String),
+
+    /// A [`PriceStore`](crate::storage::PriceStore) backend failed to persist a document.
+    #[error("Storage error: {0}")]
+    Storage(Box<dyn std::error::Error + Send + Sync>),
+}
+
+impl EntsoeError {
+    /// Whether repeating the same request might succeed.
+    ///
+    /// True for connection and timeout failures, rate limiting and 5xx API responses; false for
+    /// invalid input, 4xx API responses and documents that failed to parse.
+    #[must_use]
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            EntsoeError::Http(e) => e.is_timeout() || e.is_connect() || e.is_request(),
+            EntsoeError::ApiError { status, .. } => *status >= 500,
+            EntsoeError::RateLimited { .. } | EntsoeError::Timeout => true,
+            EntsoeError::UrlError(_)
+            | EntsoeError::InvalidBiddingZone(_)
+            | EntsoeError::InvalidTimeRange(_)
+            | EntsoeError::InvalidResolution(_)
+            | EntsoeError::Acknowledgement { .. }
+            | EntsoeError::InvalidHeader(_)
+            | EntsoeError::Unauthorized { .. }
+            | EntsoeError::RetriesExhausted { .. }
+            | EntsoeError::XmlParseError(_)
+            | EntsoeError::MissingField(_)
+            | EntsoeError::Storage(_) => false,
+        }
+    }
+
+    /// HTTP status a web service wrapping the crate could answer with for this error.
+    ///
+    /// Invalid input maps to 400, documents that don't parse to 422, and "no matching data"
+    /// acknowledgements to 404. Upstream failures map to 401 when the API rejected the token, 429
+    /// when rate limited, 503 for 5xx API responses, 504 for timeouts and 502 otherwise.
+    /// Misconfiguration of the client or storage is 500. [`EntsoeError::RetriesExhausted`]
+    /// reports the status of its last error.
+    #[must_use]
+    pub fn suggested_http_status(&self) -> u16 {
+        match self {
+            EntsoeError::InvalidBiddingZone(_)
+            | EntsoeError::InvalidTimeRange(_)
+            | EntsoeError::InvalidResolution(_) => 400,
+            EntsoeError::Unauthorized { .. }
+            | EntsoeError::ApiError {
+                status: 401 | 403, ..
+            } => 401,
+            EntsoeError::Acknowledgement { .. } => 404,
+            EntsoeError::XmlParseError(_) | EntsoeError::MissingField(_) => 422,
+            EntsoeError::RateLimited { .. } => 429,
+            EntsoeError::UrlError(_) | EntsoeError::InvalidHeader(_) | EntsoeError::Storage(_) => {
+                500
+            }
+            EntsoeError::Http(e) if e.is_timeout() => 504,
+            EntsoeError::ApiError { status, .. } if *status >= 500 => 503,
+            EntsoeError::Http(_) | EntsoeError::ApiError { .. } => 502,
+            EntsoeError::Timeout => 504,
+            EntsoeError::RetriesExhausted { last, .. } => last.suggested_http_status(),
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, EntsoeError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn api_error(status: u16) -> EntsoeError {
+        EntsoeError::ApiError {
+            status,
+            url: "https://example.com/api".to_string(),
+            body: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_http_connect_error_is_retryable() {
+        // Nothing listens on port 1
+        let err: EntsoeError = reqwest::get("http://127.0.0.1:1/")
+            .await
+            .unwrap_err()
+            .into();
+        assert!(err.is_retryable());
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(api_error(500).is_retryable());
+        assert!(api_error(503).is_retryable());
+        assert!(EntsoeError::RateLimited { url: String::new() }.is_retryable());
+        assert!(EntsoeError::Timeout.is_retryable());
+
+        assert!(!api_error(400).is_retryable());
+        assert!(!api_error(401).is_retryable());
+        assert!(!api_error(404).is_retryable());
+        assert!(
+            !EntsoeError::Unauthorized {
+                status: 401,
+                url: String::new()
+            }
+            .is_retryable()
+        );
+        assert!(!EntsoeError::InvalidTimeRange("end before start".to_string()).is_retryable());
+        assert!(!EntsoeError::InvalidBiddingZone("XX".to_string()).is_retryable());
+        assert!(!EntsoeError::MissingField("resolution".to_string()).is_retryable());
+        assert!(!EntsoeError::XmlParseError("bad".to_string()).is_retryable());
+        assert!(
+            !EntsoeError::Acknowledgement {
+                code: "999".to_string(),
+                text: "No matching data found".to_string()
+            }
+            .is_retryable()
+        );
+        assert!(!EntsoeError::from(url::Url::parse("not a url").unwrap_err()).is_retryable());
+        assert!(
+            !EntsoeError::RetriesExhausted {
+                attempts: 3,
+                last: Box::new(api_error(503))
+            }
+            .is_retryable()
+        );
+    }
+
+    #[test]
+    fn test_suggested_http_status() {
+        let http = reqwest::Client::new().get("not a url").build().unwrap_err();
+        let cases = [
+            (EntsoeError::Http(http), 502),
+            (url::Url::parse("").unwrap_err().into(), 500),
+            (EntsoeError::InvalidBiddingZone("XX".to_string()), 400),
+            (EntsoeError::InvalidTimeRange(String::new()), 400),
+            (EntsoeError::InvalidResolution(String::new()), 400),
+            (EntsoeError::InvalidHeader(String::new()), 500),
+            (api_error(401), 401),
+            (api_error(403), 401),
+            (
+                EntsoeError::Unauthorized {
+                    status: 403,
+                    url: String::new(),
+                },
+                401,
+            ),
+            (api_error(400), 502),
+            (api_error(500), 503),
+            (EntsoeError::RateLimited { url: String::new() }, 429),
+            (
+                EntsoeError::Acknowledgement {
+                    code: "999".to_string(),
+                    text: "No matching data found".to_string(),
+                },
+                404,
+            ),
+            (EntsoeError::Timeout, 504),
+            (
+                EntsoeError::RetriesExhausted {
+                    attempts: 3,
+                    last: Box::new(api_error(503)),
+                },
+                503,
+            ),
+            (EntsoeError::XmlParseError(String::new()), 422),
+            (EntsoeError::MissingField("resolution".to_string()), 422),
+            (EntsoeError::Storage("disk full".into()), 500),
+        ];
+
+        for (err, status) in cases {
+            assert_eq!(err.suggested_http_status(), status, "{err:?}");
+        }
+    }
+}
@@ -7,14 +7,35 @@
 #![allow(clippy::too_many_lines)]
 #![allow(clippy::module_name_repetitions)]
 
+pub mod analysis;
 pub mod bidding_zone;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+mod cache;
 pub mod client;
 pub mod error;
 pub mod models;
+pub mod params;
 pub mod parser;
+pub mod storage;
+#[cfg(feature = "metrics")]
+mod telemetry;
+pub mod transport;
+pub mod util;
 
 pub use bidding_zone::BiddingZone;
-pub use client::EntsoeClient;
+pub use client::{EntsoeClient, EntsoeClientBuilder};
 pub use error::{EntsoeError, Result};
-pub use models::{PriceDocument, PricePoint, Resolution};
-pub use parser::parse_day_ahead_prices;
+pub use models::{
+    DetailedPriceDocument, FlowDocument, FlowPoint, GenerationDocument, GenerationForecastDocument,
+    GenerationPoint, GenerationSeries, InstalledCapacityDocument, LoadDocument, LoadPoint,
+    PriceAnomaly, PriceDocument, PricePoint, PriceUnit, PsrType, Resolution,
+};
+pub use params::{DocumentType, Domain, ProcessType};
+pub use parser::{
+    ParseOptions, parse_actual_generation, parse_day_ahead_prices, parse_day_ahead_prices_detailed,
+    parse_day_ahead_prices_multi, parse_day_ahead_prices_stream,
+    parse_day_ahead_prices_with_options, parse_generation_forecast, parse_installed_capacity,
+    parse_physical_flows, parse_total_load,
+};
+pub use transport::{HttpTransport, ReqwestTransport};
@@ -0,0 +1,16 @@
+use std::collections::HashMap;
+
+use crate::bidding_zone::BiddingZone;
+use crate::models::psr_type::PsrType;
+
+/// Installed generation capacity per production type (documentType A68).
+///
+/// Capacities are in MW.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstalledCapacityDocument {
+    pub zone: BiddingZone,
+    pub year: i32,
+    /// Quantity unit as declared by the document (`quantity_Measure_Unit.name`), normally `MAW`.
+    pub measure_unit: Option<String>,
+    pub by_type: HashMap<PsrType, f64>,
+}
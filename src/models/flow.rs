@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+
+use crate::models::price::Resolution;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowPoint {
+    pub timestamp: DateTime<Utc>,
+    pub quantity_mw: f64,
+}
+
+/// Cross-border physical flow between two zones (documentType A11).
+///
+/// Quantities are in MW in the requested direction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowDocument {
+    pub resolution: Resolution,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Quantity unit as declared by the document (`quantity_Measure_Unit.name`), normally `MAW`.
+    pub measure_unit: Option<String>,
+    pub points: Vec<FlowPoint>,
+}
@@ -0,0 +1,47 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::models::price::Resolution;
+use crate::models::psr_type::PsrType;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationPoint {
+    pub timestamp: DateTime<Utc>,
+    pub quantity_mw: f64,
+}
+
+/// Generation time series for a single production type.
+///
+/// `psr_type` holds the ENTSO-E B-code (e.g. `B16` for solar), or `None` for aggregated totals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationSeries {
+    pub psr_type: Option<String>,
+    pub points: Vec<GenerationPoint>,
+}
+
+/// Day-ahead generation forecast from ENTSO-E (documentType A71).
+///
+/// Quantities are in MW.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationForecastDocument {
+    pub resolution: Resolution,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Quantity unit as declared by the document (`quantity_Measure_Unit.name`), normally `MAW`.
+    pub measure_unit: Option<String>,
+    pub series: Vec<GenerationSeries>,
+}
+
+/// Actual generation per production type from ENTSO-E (documentType A75).
+///
+/// Quantities are in MW. Series with unknown production types are dropped.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenerationDocument {
+    pub resolution: Resolution,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Quantity unit as declared by the document (`quantity_Measure_Unit.name`), normally `MAW`.
+    pub measure_unit: Option<String>,
+    pub by_type: HashMap<PsrType, Vec<GenerationPoint>>,
+}
@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+
+use crate::models::price::Resolution;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadPoint {
+    pub timestamp: DateTime<Utc>,
+    pub quantity_mw: f64,
+}
+
+/// Total load from ENTSO-E (documentType A65), either realised or forecast.
+///
+/// Quantities are in MW.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadDocument {
+    pub resolution: Resolution,
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Quantity unit as declared by the document (`quantity_Measure_Unit.name`), normally `MAW`.
+    pub measure_unit: Option<String>,
+    pub points: Vec<LoadPoint>,
+}
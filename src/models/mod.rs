@@ -1,3 +1,19 @@
+pub mod capacity;
+pub mod flow;
+pub mod generation;
+pub mod load;
 pub mod price;
+pub mod psr_type;
 
-pub use price::{PriceDocument, PricePoint, Resolution};
+pub use capacity::InstalledCapacityDocument;
+pub use flow::{FlowDocument, FlowPoint};
+pub use generation::{
+    GenerationDocument, GenerationForecastDocument, GenerationPoint, GenerationSeries,
+};
+pub use load::{LoadDocument, LoadPoint};
+#[cfg(feature = "tabled")]
+pub use price::PriceRow;
+pub use price::{
+    DetailedPriceDocument, Price, PriceAnomaly, PriceDocument, PricePoint, PriceUnit, Resolution,
+};
+pub use psr_type::PsrType;
@@ -1,9 +1,50 @@
-use chrono::{DateTime, Utc};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io::{self, Write};
+
+use chrono::{DateTime, Duration, DurationRound, NaiveDate, NaiveTime, Timelike, Utc};
+use chrono_tz::Tz;
+
+use crate::analysis::{WindowOrder, rank_windows};
+use crate::bidding_zone::BiddingZone;
+use crate::error::{EntsoeError, Result};
+
+/// Numeric type of prices: `f64` by default, `rust_decimal::Decimal` with the `decimal` feature.
+#[cfg(not(feature = "decimal"))]
+pub type Price = f64;
+
+/// Numeric type of prices: `f64` by default, `rust_decimal::Decimal` with the `decimal` feature.
+#[cfg(feature = "decimal")]
+pub type Price = rust_decimal::Decimal;
+
+/// Converts a price for statistics that are computed in floating point under either feature.
+#[cfg(not(feature = "decimal"))]
+pub(crate) fn price_to_f64(price: Price) -> f64 {
+    price
+}
+
+#[cfg(feature = "decimal")]
+pub(crate) fn price_to_f64(price: Price) -> f64 {
+    rust_decimal::prelude::ToPrimitive::to_f64(&price).unwrap_or(f64::NAN)
+}
+
+#[cfg(not(feature = "decimal"))]
+pub(crate) fn price_from_f64(value: f64) -> Price {
+    value
+}
+
+#[cfg(feature = "decimal")]
+pub(crate) fn price_from_f64(value: f64) -> Price {
+    rust_decimal::prelude::FromPrimitive::from_f64(value).unwrap_or_default()
+}
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct PricePoint {
     pub timestamp: DateTime<Utc>,
-    pub price: f64,
+    pub price: Price,
+    /// The point's `quality` marker as given in the document, e.g. to tell provisional prices
+    /// from final ones. `None` for points without one and for computed points.
+    pub quality: Option<String>,
 }
 
 impl PricePoint {
@@ -12,24 +53,889 @@ impl PricePoint {
     /// The ENTSO-E API returns prices in EUR/MWh, this method converts to EUR/kWh
     /// by dividing by 1000.
     #[must_use]
-    pub fn price_per_kwh(&self) -> f64 {
-        self.price / 1000.0
+    pub fn price_per_kwh(&self) -> Price {
+        self.price / Price::from(1000)
+    }
+
+    /// Returns the price in EUR/MWh, as delivered by the API. Same as the `price` field.
+    #[must_use]
+    pub fn price_per_mwh(&self) -> Price {
+        self.price
+    }
+
+    /// Returns the price in the given energy unit.
+    #[must_use]
+    pub fn price_in(&self, unit: PriceUnit) -> Price {
+        match unit {
+            PriceUnit::PerMwh => self.price_per_mwh(),
+            PriceUnit::PerKwh => self.price_per_kwh(),
+        }
+    }
+}
+
+/// A price point in local time, for rendering with the `tabled` crate.
+#[cfg(feature = "tabled")]
+#[derive(Debug, Clone, PartialEq, tabled::Tabled)]
+pub struct PriceRow {
+    #[tabled(rename = "Time")]
+    pub time: DateTime<Tz>,
+    #[tabled(rename = "Price")]
+    pub price: Price,
+}
+
+impl<'a> IntoIterator for &'a PriceDocument {
+    type Item = &'a PricePoint;
+    type IntoIter = std::slice::Iter<'a, PricePoint>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.prices.iter()
+    }
+}
+
+/// Compact summary: a header line with currency, resolution, period, point count and
+/// min/max/mean price, then one aligned `timestamp  price` row per point.
+impl fmt::Display for PriceDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const TIME_FORMAT: &str = "%Y-%m-%d %H:%M UTC";
+        write!(
+            f,
+            "{} {:?} {} - {}, {} points",
+            self.currency,
+            self.resolution,
+            self.period_start.format(TIME_FORMAT),
+            self.period_end.format(TIME_FORMAT),
+            self.prices.len()
+        )?;
+        let prices = self.prices.iter().map(|p| p.price);
+        if let (Some(min), Some(max)) = (
+            prices.clone().reduce(|a, b| if b < a { b } else { a }),
+            prices.clone().reduce(|a, b| if b > a { b } else { a }),
+        ) {
+            let mean = prices.sum::<Price>() / Price::from(u32::try_from(self.len()).unwrap_or(1));
+            write!(
+                f,
+                ", min {min:.2} max {max:.2} mean {mean:.2} {}/MWh",
+                self.currency
+            )?;
+        }
+
+        let formatted: Vec<String> = self
+            .prices
+            .iter()
+            .map(|p| format!("{:.2}", p.price))
+            .collect();
+        let width = formatted.iter().map(String::len).max().unwrap_or(0);
+        for (point, price) in self.prices.iter().zip(&formatted) {
+            write!(
+                f,
+                "\n{}  {price:>width$}",
+                point.timestamp.format(TIME_FORMAT)
+            )?;
+        }
+        Ok(())
     }
 }
 
+/// A point flagged by [`PriceDocument::validate`]. Advisory: the document is still usable.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PriceAnomaly {
+    /// The price lies outside the plausible band, which usually means a parse or source error
+    /// rather than a real market price.
+    OutOfRange { point: PricePoint, reason: String },
+}
+
+/// Energy unit a price is expressed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PriceUnit {
+    PerMwh,
+    PerKwh,
+}
+
 /// Document containing electricity price data from ENTSO-E.
 ///
 /// Day-ahead prices are in EUR/MWh for all zones (ENTSO-E standard).
 /// Use `PricePoint::price_per_kwh()` to convert to EUR/kWh.
 #[derive(Debug, Clone, PartialEq)]
 pub struct PriceDocument {
+    /// Publication time of the document (`createdDateTime`), if present.
+    pub created_at: Option<DateTime<Utc>>,
     pub currency: String,
+    /// Resolution of the first period in the document.
     pub resolution: Resolution,
+    /// Distinct resolutions in the order they appear, so mixed-resolution documents can be detected.
+    pub resolutions_seen: Vec<Resolution>,
+    /// Start of the earliest period (inclusive).
     pub period_start: DateTime<Utc>,
+    /// End of the latest period (exclusive): the instant after the last period ends, not the start
+    /// of the last point. See [`PriceDocument::last_point_end`].
     pub period_end: DateTime<Utc>,
+    /// Lower price bound declared by the document (`minPriceRange`), if any.
+    pub min_price_range: Option<Price>,
+    /// Upper price bound declared by the document (`maxPriceRange`), if any.
+    pub max_price_range: Option<Price>,
+    /// Curve type of the first time series (`curveType`), e.g. `A01` for sequential fixed-size
+    /// blocks or `A03` for variable-size blocks where repeated prices are omitted.
+    pub curve_type: Option<String>,
+    /// Business type of the first time series (`businessType`), e.g. `A62` for spot prices.
+    pub business_type: Option<String>,
     pub prices: Vec<PricePoint>,
 }
 
+/// A price document with the source `position` of each point, from
+/// [`parse_day_ahead_prices_detailed`](crate::parser::price_parser::parse_day_ahead_prices_detailed).
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetailedPriceDocument {
+    pub document: PriceDocument,
+    /// 1-based `position` of each point within its period in the XML, parallel to
+    /// `document.prices`.
+    pub positions: Vec<u32>,
+}
+
+impl PriceDocument {
+    /// Header of the CSV written by [`PriceDocument::write_csv`] and the `entsoe-csv` binary.
+    pub const CSV_HEADER: &'static str = "timestamp,price_per_kwh,currency,price_area";
+
+    /// Returns the (exclusive) end of the final price point's interval.
+    ///
+    /// For a contiguous document this equals `period_end`; it is earlier when trailing positions
+    /// are missing.
+    #[must_use]
+    pub fn last_point_end(&self) -> Option<DateTime<Utc>> {
+        Some(self.point_end(self.prices.len().checked_sub(1)?))
+    }
+
+    /// Iterates over the price points in time order.
+    pub fn iter(&self) -> std::slice::Iter<'_, PricePoint> {
+        self.prices.iter()
+    }
+
+    /// Number of price points.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.prices.len()
+    }
+
+    /// Whether the document has no price points.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.prices.is_empty()
+    }
+
+    /// The point whose period contains `t`.
+    ///
+    /// A point covers `[timestamp, timestamp + resolution)`, cut short by the next point; in A03
+    /// curves it covers everything up to the next point. Returns `None` outside the document or
+    /// inside a gap left by missing positions.
+    #[must_use]
+    pub fn price_at(&self, t: DateTime<Utc>) -> Option<&PricePoint> {
+        let index = self
+            .prices
+            .partition_point(|p| p.timestamp <= t)
+            .checked_sub(1)?;
+        (t < self.point_end(index)).then(|| &self.prices[index])
+    }
+
+    /// Price in EUR/MWh of the period containing `instant`. See [`PriceDocument::price_at`].
+    #[must_use]
+    pub fn price_at_instant(&self, instant: DateTime<Utc>) -> Option<Price> {
+        self.price_at(instant).map(|p| p.price)
+    }
+
+    /// Whether the price at `now` is at or below the `quantile` (0.0–1.0) of all prices in the
+    /// document, e.g. `0.25` for "in the cheapest quartile".
+    ///
+    /// Quantiles interpolate linearly between the sorted prices. Returns `None` when no point
+    /// covers `now`.
+    #[must_use]
+    pub fn is_now_cheap(&self, now: DateTime<Utc>, quantile: f64) -> Option<bool> {
+        let current = price_to_f64(self.price_at_instant(now)?);
+        let mut sorted: Vec<f64> = self.prices.iter().map(|p| price_to_f64(p.price)).collect();
+        sorted.sort_by(f64::total_cmp);
+        Some(current <= quantile_of_sorted(&sorted, quantile)?)
+    }
+
+    /// Splits the points at the median price into `(at or below median, above median)`, each in
+    /// time order.
+    ///
+    /// With an even number of points the median is the mean of the two middle prices. Points
+    /// equal to the median go to the cheap half.
+    #[must_use]
+    pub fn split_at_median(&self) -> (Vec<&PricePoint>, Vec<&PricePoint>) {
+        let mut sorted: Vec<f64> = self.prices.iter().map(|p| price_to_f64(p.price)).collect();
+        sorted.sort_by(f64::total_cmp);
+        let Some(median) = quantile_of_sorted(&sorted, 0.5) else {
+            return (Vec::new(), Vec::new());
+        };
+        self.prices
+            .iter()
+            .partition(|p| price_to_f64(p.price) <= median)
+    }
+
+    /// Cost in EUR of running a constant `power_kw` load for `duration` from `start`.
+    ///
+    /// Each covered period contributes `power_kw * price_per_kwh * hours`, pro rata for periods
+    /// cut by the window edges. Computed in floating point. Returns `None` if any part of the
+    /// window has no price.
+    #[must_use]
+    pub fn cost_constant_power(
+        &self,
+        start: DateTime<Utc>,
+        duration: Duration,
+        power_kw: f64,
+    ) -> Option<f64> {
+        if duration < Duration::zero() {
+            return None;
+        }
+        let end = start + duration;
+
+        let mut cost = 0.0;
+        let mut covered = Duration::zero();
+        for (index, point) in self.prices.iter().enumerate() {
+            let overlap = self.point_end(index).min(end) - point.timestamp.max(start);
+            if overlap > Duration::zero() {
+                let hours = f64::from(i32::try_from(overlap.num_seconds()).ok()?) / 3600.0;
+                cost += power_kw * price_to_f64(point.price_per_kwh()) * hours;
+                covered += overlap;
+            }
+        }
+
+        (covered == duration).then_some(cost)
+    }
+
+    /// Mean price in EUR/MWh, weighting each point by the length of its period.
+    ///
+    /// Differs from the plain mean when resolutions are mixed. Returns `None` for an empty
+    /// document.
+    #[must_use]
+    pub fn time_weighted_mean(&self) -> Option<Price> {
+        let mut weighted_sum = Price::default();
+        let mut total_minutes = 0;
+        for (index, point) in self.prices.iter().enumerate() {
+            let end = self.point_end(index).min(self.period_end);
+            let minutes = u32::try_from((end - point.timestamp).num_minutes()).ok()?;
+            weighted_sum += point.price * Price::from(minutes);
+            total_minutes += minutes;
+        }
+        (total_minutes > 0).then(|| weighted_sum / Price::from(total_minutes))
+    }
+
+    /// Average price in EUR/kWh for a flat consumption profile, i.e. the
+    /// [time-weighted mean](Self::time_weighted_mean) divided by 1000.
+    #[must_use]
+    pub fn flat_consumption_price_kwh(&self) -> Option<Price> {
+        Some(self.time_weighted_mean()? / Price::from(1000))
+    }
+
+    /// Finds the cheapest run of `n` consecutive periods.
+    ///
+    /// `n` counts periods of the document's finest resolution, so three hours is `n = 12` for
+    /// PT15M data. Windows span time rather than points: positions missing from the document,
+    /// such as repeated prices left out of A03 curves, count at the price of the point before
+    /// them. Returns the window start, its exclusive end and the average price in EUR/MWh.
+    /// Ties resolve to the earliest window.
+    #[must_use]
+    pub fn cheapest_window(&self, n: usize) -> Option<(DateTime<Utc>, DateTime<Utc>, Price)> {
+        self.find_window(n, WindowOrder::Cheapest)
+    }
+
+    /// Finds the most expensive run of `n` consecutive periods.
+    ///
+    /// Same conventions as [`PriceDocument::cheapest_window`].
+    #[must_use]
+    pub fn most_expensive_window(&self, n: usize) -> Option<(DateTime<Utc>, DateTime<Utc>, Price)> {
+        self.find_window(n, WindowOrder::MostExpensive)
+    }
+
+    /// Every run of `periods` consecutive periods whose average price is below
+    /// `ceiling_eur_mwh`.
+    ///
+    /// Periods follow the conventions of [`PriceDocument::cheapest_window`]. Returns each
+    /// qualifying window's start and average price in EUR/MWh, in time order. Windows overlap, so
+    /// a long cheap stretch yields one entry per starting period.
+    #[must_use]
+    pub fn windows_below_avg(
+        &self,
+        periods: usize,
+        ceiling_eur_mwh: f64,
+    ) -> Vec<(DateTime<Utc>, f64)> {
+        let Some(count) = u32::try_from(periods).ok().filter(|&n| n > 0) else {
+            return Vec::new();
+        };
+        self.filled_periods()
+            .windows(periods)
+            .filter_map(|window| {
+                let sum: f64 = window.iter().map(|&(_, price)| price_to_f64(price)).sum();
+                let avg = sum / f64::from(count);
+                (avg < ceiling_eur_mwh).then_some((window[0].0, avg))
+            })
+            .collect()
+    }
+
+    /// Per-kWh value of moving consumption from the priciest to the cheapest period, in EUR/kWh.
+    ///
+    /// Returns `None` for an empty document.
+    #[must_use]
+    pub fn flex_value_per_kwh(&self) -> Option<Price> {
+        let prices = self.prices.iter().map(|p| p.price);
+        let max = prices.clone().reduce(|a, b| if b > a { b } else { a })?;
+        let min = prices.reduce(|a, b| if b < a { b } else { a })?;
+        Some((max - min) / Price::from(1000))
+    }
+
+    /// Coefficient of variation of the prices: population standard deviation divided by the
+    /// mean, for comparing volatility across markets with different price levels.
+    ///
+    /// Returns `None` for an empty document or when the mean is zero or negative, where the ratio
+    /// is undefined or flips sign.
+    #[must_use]
+    pub fn coefficient_of_variation(&self) -> Option<f64> {
+        let n = f64::from(u32::try_from(self.prices.len()).ok()?);
+        let mean = self
+            .prices
+            .iter()
+            .map(|p| price_to_f64(p.price))
+            .sum::<f64>()
+            / n;
+        if mean.is_nan() || mean <= 0.0 {
+            return None;
+        }
+        let variance = self
+            .prices
+            .iter()
+            .map(|p| (price_to_f64(p.price) - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        Some(variance.sqrt() / mean)
+    }
+
+    /// Mean price in EUR/MWh per named time-of-use bucket.
+    ///
+    /// Each bucket is `(name, start, end)` in local time of `tz`, half-open (`start <= t < end`).
+    /// A bucket whose end is not after its start wraps past midnight, so `22:00..06:00` covers the
+    /// night. Points are classified by the local time at which their period starts and count
+    /// towards every bucket that contains them. Buckets without points are left out.
+    #[must_use]
+    pub fn bucket_means(
+        &self,
+        tz: Tz,
+        buckets: &[(String, NaiveTime, NaiveTime)],
+    ) -> HashMap<String, Price> {
+        let mut sums: HashMap<&str, (Price, u32)> = HashMap::new();
+
+        for point in &self.prices {
+            let local = point.timestamp.with_timezone(&tz).time();
+            for (name, start, end) in buckets {
+                let contains = if start < end {
+                    *start <= local && local < *end
+                } else {
+                    local >= *start || local < *end
+                };
+                if contains {
+                    let entry = sums.entry(name.as_str()).or_default();
+                    entry.0 += point.price;
+                    entry.1 += 1;
+                }
+            }
+        }
+
+        sums.into_iter()
+            .map(|(name, (sum, count))| (name.to_string(), sum / Price::from(count)))
+            .collect()
+    }
+
+    /// Pearson correlation between each point's price and its local hour of day (0–23) in `tz`.
+    ///
+    /// A rough peakiness metric. Returns `None` with fewer than two points or when either price or
+    /// hour is constant.
+    #[must_use]
+    pub fn price_hour_correlation(&self, tz: Tz) -> Option<f64> {
+        if self.prices.len() < 2 {
+            return None;
+        }
+
+        let pairs: Vec<(f64, f64)> = self
+            .prices
+            .iter()
+            .map(|p| {
+                let hour = f64::from(p.timestamp.with_timezone(&tz).hour());
+                (hour, price_to_f64(p.price))
+            })
+            .collect();
+        let n = f64::from(u32::try_from(pairs.len()).ok()?);
+        let mean_hour = pairs.iter().map(|(h, _)| h).sum::<f64>() / n;
+        let mean_price = pairs.iter().map(|(_, p)| p).sum::<f64>() / n;
+
+        let (mut covariance, mut var_hour, mut var_price) = (0.0, 0.0, 0.0);
+        for (hour, price) in &pairs {
+            let dh = hour - mean_hour;
+            let dp = price - mean_price;
+            covariance += dh * dp;
+            var_hour += dh * dh;
+            var_price += dp * dp;
+        }
+
+        if var_hour == 0.0 || var_price == 0.0 {
+            return None;
+        }
+        Some(covariance / (var_hour * var_price).sqrt())
+    }
+
+    /// Savings in EUR from moving `power_kw` of load out of each local day's priciest hour to
+    /// the day's average price.
+    ///
+    /// Days are calendar days in `tz`. Each hour's price is the mean of its points, and a day
+    /// contributes `power_kw * (peak_hour_price - mean_hour_price) / 1000`. Hours are keyed by
+    /// their absolute start, so the repeated hour on a fall-back day counts separately.
+    #[must_use]
+    pub fn peak_avoidance_savings(&self, tz: Tz, power_kw: f64) -> f64 {
+        let mut days: BTreeMap<NaiveDate, BTreeMap<DateTime<Utc>, (f64, u32)>> = BTreeMap::new();
+        for point in &self.prices {
+            let local = point.timestamp.with_timezone(&tz);
+            let hour_start = point.timestamp
+                - Duration::minutes(i64::from(local.minute()))
+                - Duration::seconds(i64::from(local.second()));
+            let hour = days
+                .entry(local.date_naive())
+                .or_default()
+                .entry(hour_start)
+                .or_default();
+            hour.0 += price_to_f64(point.price);
+            hour.1 += 1;
+        }
+
+        days.values()
+            .filter_map(|hours| {
+                let hourly: Vec<f64> = hours
+                    .values()
+                    .map(|(sum, count)| sum / f64::from(*count))
+                    .collect();
+                let peak = hourly.iter().copied().reduce(f64::max)?;
+                let mean =
+                    hourly.iter().sum::<f64>() / f64::from(u32::try_from(hourly.len()).ok()?);
+                Some(power_kw * (peak - mean) / 1000.0)
+            })
+            .sum()
+    }
+
+    /// Each point's timestamp in `tz` with its price in EUR/MWh.
+    #[must_use]
+    pub fn localized(&self, tz: Tz) -> Vec<(DateTime<Tz>, Price)> {
+        self.prices
+            .iter()
+            .map(|p| (p.timestamp.with_timezone(&tz), p.price))
+            .collect()
+    }
+
+    /// One table row per price point, with timestamps converted to `tz`.
+    #[cfg(feature = "tabled")]
+    #[must_use]
+    pub fn to_table_rows(&self, tz: Tz) -> Vec<PriceRow> {
+        self.prices
+            .iter()
+            .map(|p| PriceRow {
+                time: p.timestamp.with_timezone(&tz),
+                price: p.price,
+            })
+            .collect()
+    }
+
+    /// Timestamps and prices in EUR/MWh as parallel columns, in time order.
+    #[must_use]
+    pub fn to_columns(&self) -> (Vec<DateTime<Utc>>, Vec<f64>) {
+        self.prices
+            .iter()
+            .map(|p| (p.timestamp, price_to_f64(p.price)))
+            .unzip()
+    }
+
+    /// The prices as an Arrow record batch with a `timestamp` column (milliseconds, UTC) and a
+    /// `price_eur_mwh` column.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if Arrow rejects the columns, which should not happen for a parsed document.
+    #[cfg(feature = "arrow")]
+    pub fn to_record_batch(
+        &self,
+    ) -> std::result::Result<arrow::record_batch::RecordBatch, arrow::error::ArrowError> {
+        use std::sync::Arc;
+
+        use arrow::array::{ArrayRef, Float64Array, TimestampMillisecondArray};
+        use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
+
+        let (timestamps, prices) = self.to_columns();
+        let timestamps = TimestampMillisecondArray::from(
+            timestamps
+                .iter()
+                .map(DateTime::timestamp_millis)
+                .collect::<Vec<_>>(),
+        )
+        .with_timezone("UTC");
+
+        let schema = Schema::new(vec![
+            Field::new(
+                "timestamp",
+                DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())),
+                false,
+            ),
+            Field::new("price_eur_mwh", DataType::Float64, false),
+        ]);
+        let columns: Vec<ArrayRef> =
+            vec![Arc::new(timestamps), Arc::new(Float64Array::from(prices))];
+        arrow::record_batch::RecordBatch::try_new(Arc::new(schema), columns)
+    }
+
+    /// Timestamps as Unix epoch seconds and prices in EUR/MWh as parallel arrays.
+    #[cfg(feature = "ndarray")]
+    #[must_use]
+    pub fn to_ndarray(&self) -> (ndarray::Array1<i64>, ndarray::Array1<f64>) {
+        let timestamps = self
+            .prices
+            .iter()
+            .map(|p| p.timestamp.timestamp())
+            .collect();
+        let prices = self.prices.iter().map(|p| price_to_f64(p.price)).collect();
+        (timestamps, prices)
+    }
+
+    /// Hash of the `(timestamp, price)` pairs for change detection.
+    ///
+    /// Points are hashed in timestamp order with FNV-1a, so the value is stable across runs and
+    /// platforms and ignores metadata such as `created_at`.
+    #[must_use]
+    pub fn content_hash(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+        let mut points: Vec<(i64, u64)> = self
+            .prices
+            .iter()
+            .map(|p| {
+                // Adding 0.0 turns -0.0 into 0.0
+                let price = price_to_f64(p.price) + 0.0;
+                (p.timestamp.timestamp(), price.to_bits())
+            })
+            .collect();
+        points.sort_unstable();
+
+        points
+            .iter()
+            .flat_map(|(timestamp, price)| {
+                timestamp
+                    .to_le_bytes()
+                    .into_iter()
+                    .chain(price.to_le_bytes())
+            })
+            .fold(FNV_OFFSET, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+            })
+    }
+
+    /// Returns a copy with every price multiplied by `rate` and `currency` set to `to`.
+    ///
+    /// `rate` is units of `to` per unit of the current currency, e.g. about `0.085` EUR per NOK.
+    /// The crate fetches no exchange rates itself; the caller supplies the rate, and it applies
+    /// uniformly to the whole document. Declared price ranges are converted too.
+    #[must_use]
+    pub fn convert_currency(&self, to: &str, rate: f64) -> PriceDocument {
+        let rate = price_from_f64(rate);
+        let mut converted = self.clone();
+        converted.currency = to.to_string();
+        converted.min_price_range = self.min_price_range.map(|min| min * rate);
+        converted.max_price_range = self.max_price_range.map(|max| max * rate);
+        for point in &mut converted.prices {
+            point.price *= rate;
+        }
+        converted
+    }
+
+    /// Returns a copy downsampled to the coarser `target` resolution, e.g. PT15M to hourly.
+    ///
+    /// Each target interval, aligned to a multiple of its length since the Unix epoch, gets the
+    /// mean price over the time covered by points, so positions left out of A03 curves count at
+    /// the price that repeats through them. Gaps in other curves are skipped, and intervals
+    /// without any covered time are left out. Resampling to the current resolution returns an
+    /// unchanged copy.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EntsoeError::InvalidResolution`] if any period of the document is coarser than
+    /// `target`, since upsampling would have to invent prices.
+    pub fn resample(&self, target: Resolution) -> Result<PriceDocument> {
+        if let Some(coarser) = self
+            .resolutions_seen
+            .iter()
+            .chain([&self.resolution])
+            .find(|r| r.minutes() > target.minutes())
+        {
+            return Err(EntsoeError::InvalidResolution(format!(
+                "cannot upsample {coarser:?} data to {target:?}"
+            )));
+        }
+
+        let interval = Duration::minutes(target.minutes());
+        let align = |t: DateTime<Utc>| t.duration_trunc(interval).unwrap_or(t);
+        let mut buckets: BTreeMap<DateTime<Utc>, (Price, u32)> = BTreeMap::new();
+        for (index, point) in self.prices.iter().enumerate() {
+            let end = self.point_end(index);
+            let mut t = point.timestamp;
+            while t < end {
+                let bucket_start = align(t);
+                let until = (bucket_start + interval).min(end);
+                let minutes = u32::try_from((until - t).num_minutes()).unwrap_or_default();
+                let bucket = buckets.entry(bucket_start).or_default();
+                bucket.0 += point.price * Price::from(minutes);
+                bucket.1 += minutes;
+                t = until;
+            }
+        }
+
+        Ok(PriceDocument {
+            resolution: target,
+            resolutions_seen: vec![target],
+            period_start: align(self.period_start),
+            prices: buckets
+                .into_iter()
+                .filter(|&(_, (_, minutes))| minutes > 0)
+                .map(|(timestamp, (sum, minutes))| PricePoint {
+                    timestamp,
+                    price: sum / Price::from(minutes),
+                    quality: None,
+                })
+                .collect(),
+            ..self.clone()
+        })
+    }
+
+    /// Formats the prices as `InfluxDB` line protocol, one newline-terminated line per point:
+    /// `measurement,zone=FI price=42.1 1728770400000000000`.
+    ///
+    /// `price` is in the document's currency/MWh and timestamps are in nanoseconds. Commas,
+    /// spaces and (in the tag) equals signs are backslash-escaped. Points outside the range
+    /// representable in nanoseconds (years 1677–2262) are skipped.
+    #[must_use]
+    pub fn to_influx_line_protocol(&self, measurement: &str, zone: &str) -> String {
+        let escape = |s: &str, special: &[char]| {
+            let mut escaped = String::with_capacity(s.len());
+            for c in s.chars() {
+                if special.contains(&c) {
+                    escaped.push('\\');
+                }
+                escaped.push(c);
+            }
+            escaped
+        };
+        let measurement = escape(measurement, &[',', ' ']);
+        let zone = escape(zone, &[',', '=', ' ']);
+
+        self.prices
+            .iter()
+            .filter_map(|p| {
+                let nanos = p.timestamp.timestamp_nanos_opt()?;
+                Some(format!(
+                    "{measurement},zone={zone} price={} {nanos}\n",
+                    p.price
+                ))
+            })
+            .collect()
+    }
+
+    /// Writes the prices as CSV with a [`CSV_HEADER`](Self::CSV_HEADER) line, one row per point.
+    ///
+    /// Timestamps are RFC 3339 and prices are per kWh with five decimals, matching what
+    /// `entsoe-csv` exports from a database.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if writing to `w` fails.
+    pub fn write_csv(&self, zone: BiddingZone, mut w: impl Write) -> io::Result<()> {
+        writeln!(w, "{}", Self::CSV_HEADER)?;
+        for point in &self.prices {
+            writeln!(
+                w,
+                "{},{:.5},{},{}",
+                point.timestamp.to_rfc3339(),
+                point.price_per_kwh(),
+                self.currency,
+                zone.code()
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Price change per hour, in EUR/MWh per hour, at each transition between consecutive points.
+    ///
+    /// Each entry is `(timestamp of the later point, (price[i] - price[i-1]) / hours between the
+    /// two points)`, so PT15M and PT60M series are comparable.
+    #[must_use]
+    pub fn ramps(&self) -> Vec<(DateTime<Utc>, f64)> {
+        self.prices
+            .windows(2)
+            .filter_map(|pair| {
+                let seconds = (pair[1].timestamp - pair[0].timestamp).num_seconds();
+                let hours = f64::from(i32::try_from(seconds).ok()?) / 3600.0;
+                let change = price_to_f64(pair[1].price - pair[0].price);
+                (hours > 0.0).then(|| (pair[1].timestamp, change / hours))
+            })
+            .collect()
+    }
+
+    /// Whether the document was published more than `max_age` before `now`.
+    ///
+    /// Returns `None` when the document carries no `created_at`.
+    #[must_use]
+    pub fn is_stale(&self, max_age: Duration, now: DateTime<Utc>) -> Option<bool> {
+        let created_at = self.created_at?;
+        Some(now - created_at > max_age)
+    }
+
+    /// Returns the points whose price falls outside the declared `minPriceRange`/`maxPriceRange`.
+    ///
+    /// Bounds are inclusive; a missing bound is not checked.
+    #[must_use]
+    pub fn validate_price_ranges(&self) -> Vec<&PricePoint> {
+        self.prices
+            .iter()
+            .filter(|p| {
+                self.min_price_range.is_some_and(|min| p.price < min)
+                    || self.max_price_range.is_some_and(|max| p.price > max)
+            })
+            .collect()
+    }
+
+    /// Flags points whose price lies outside -500..=4000 EUR/MWh.
+    ///
+    /// Negative prices are common and not flagged on their own; values far outside the band,
+    /// like -99999, usually signal a parse or source error. See
+    /// [`PriceDocument::validate_within`] for a custom band.
+    #[must_use]
+    pub fn validate(&self) -> Vec<PriceAnomaly> {
+        self.validate_within(Price::from(-500), Price::from(4000))
+    }
+
+    /// Flags points whose price lies outside `min..=max` EUR/MWh, in time order.
+    #[must_use]
+    pub fn validate_within(&self, min: Price, max: Price) -> Vec<PriceAnomaly> {
+        self.prices
+            .iter()
+            .filter_map(|point| {
+                let reason = if point.price < min {
+                    format!("{} {}/MWh is below {min}", point.price, self.currency)
+                } else if point.price > max {
+                    format!("{} {}/MWh is above {max}", point.price, self.currency)
+                } else {
+                    return None;
+                };
+                Some(PriceAnomaly::OutOfRange {
+                    point: point.clone(),
+                    reason,
+                })
+            })
+            .collect()
+    }
+
+    /// Combines two documents for the same zone, keeping one point per timestamp.
+    ///
+    /// Metadata such as currency comes from `self`; `created_at` becomes the older of the two.
+    pub(crate) fn merge(mut self, other: PriceDocument) -> PriceDocument {
+        self.created_at = match (self.created_at, other.created_at) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+        for res in other.resolutions_seen {
+            if !self.resolutions_seen.contains(&res) {
+                self.resolutions_seen.push(res);
+            }
+        }
+        self.curve_type = self.curve_type.or(other.curve_type);
+        self.business_type = self.business_type.or(other.business_type);
+        self.period_start = self.period_start.min(other.period_start);
+        self.period_end = self.period_end.max(other.period_end);
+        self.prices.extend(other.prices);
+        self.prices.sort_by_key(|p| p.timestamp);
+        self.prices.dedup_by_key(|p| p.timestamp);
+        self
+    }
+
+    /// Exclusive end of the point at `index`: one period later, cut short by the next point or,
+    /// for the last point, by `period_end`. In A03 curves, which leave out repeated prices, a
+    /// point instead runs until the next one.
+    ///
+    /// The longest resolution seen is used so mixed-resolution documents resolve correctly. Every
+    /// method that needs a point's end goes through here.
+    fn point_end(&self, index: usize) -> DateTime<Utc> {
+        let timestamp = self.prices[index].timestamp;
+        let next = match self.prices.get(index + 1) {
+            Some(next) => Some(next.timestamp),
+            None => Some(self.period_end).filter(|&end| end > timestamp),
+        };
+        if let Some(next) = next.filter(|_| self.curve_type.as_deref() == Some("A03")) {
+            return next;
+        }
+
+        let longest = self
+            .resolutions_seen
+            .iter()
+            .map(Resolution::minutes)
+            .max()
+            .unwrap_or(self.resolution.minutes());
+        let end = timestamp + Duration::minutes(longest);
+        next.map_or(end, |next| end.min(next))
+    }
+
+    /// The shortest resolution of any period in the document.
+    pub(crate) fn finest_resolution(&self) -> Resolution {
+        self.resolutions_seen
+            .iter()
+            .copied()
+            .chain([self.resolution])
+            .min_by_key(Resolution::minutes)
+            .unwrap_or(self.resolution)
+    }
+
+    /// One price per period of the [finest resolution](Self::finest_resolution), from the first
+    /// point to the end of the last. Positions left out of the document, such as repeated prices
+    /// in A03 curves, take the price of the point before them.
+    pub(crate) fn filled_periods(&self) -> Vec<(DateTime<Utc>, Price)> {
+        let Some(end) = self.last_point_end() else {
+            return Vec::new();
+        };
+        let step = Duration::minutes(self.finest_resolution().minutes());
+
+        let mut filled = Vec::new();
+        let mut index = 0;
+        let mut t = self.prices[0].timestamp;
+        while t < end && step > Duration::zero() {
+            while self
+                .prices
+                .get(index + 1)
+                .is_some_and(|next| next.timestamp <= t)
+            {
+                index += 1;
+            }
+            filled.push((t, self.prices[index].price));
+            t += step;
+        }
+        filled
+    }
+
+    fn find_window(
+        &self,
+        n: usize,
+        order: WindowOrder,
+    ) -> Option<(DateTime<Utc>, DateTime<Utc>, Price)> {
+        let periods = self.filled_periods();
+        let prices: Vec<Price> = periods.iter().map(|&(_, price)| price).collect();
+        let &(index, avg) = rank_windows(&prices, n, 1, false, order).first()?;
+
+        let start = periods[index].0;
+        let end = periods
+            .get(index + n)
+            .map_or(self.last_point_end()?, |&(next, _)| next);
+        Some((start, end, avg))
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Resolution {
     PT15M,
@@ -53,6 +959,70 @@ impl Resolution {
             Resolution::PT60M => 60,
         }
     }
+
+    /// Human-readable label for display, e.g. "15 minutes" rather than the ISO 8601 `PT15M`.
+    #[must_use]
+    pub fn label(&self) -> &'static str {
+        match self {
+            Resolution::PT15M => "15 minutes",
+            Resolution::PT60M => "1 hour",
+        }
+    }
+}
+
+/// Linearly interpolated quantile of ascending `sorted` values; `q` is clamped to `0.0..=1.0`.
+fn quantile_of_sorted(sorted: &[f64], q: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = q.clamp(0.0, 1.0) * f64::from(u32::try_from(last).ok()?);
+    let lower = rank.floor();
+    let fraction = rank - lower;
+    // rank lies in 0..=last, so the conversion cannot truncate
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let index = lower as usize;
+    let upper = sorted.get(index + 1).unwrap_or(&sorted[index]);
+    Some(sorted[index] + (upper - sorted[index]) * fraction)
+}
+
+/// Builds a document with one point per `resolution` step from `start`, for tests.
+#[cfg(test)]
+pub(crate) fn test_document(
+    start: DateTime<Utc>,
+    resolution: Resolution,
+    prices: &[f64],
+) -> PriceDocument {
+    let step = Duration::minutes(resolution.minutes());
+    PriceDocument {
+        created_at: None,
+        currency: "EUR".to_string(),
+        resolution,
+        resolutions_seen: vec![resolution],
+        period_start: start,
+        period_end: start + step * i32::try_from(prices.len()).expect("test series fits in i32"),
+        min_price_range: None,
+        max_price_range: None,
+        curve_type: None,
+        business_type: None,
+        prices: prices
+            .iter()
+            .enumerate()
+            .map(|(i, &price)| PricePoint {
+                timestamp: start + step * i32::try_from(i).expect("test series fits in i32"),
+                price: price_from_f64(price),
+                quality: None,
+            })
+            .collect(),
+    }
+}
+
+/// [`test_document`] with hourly points from 2024-01-15 00:00 UTC.
+#[cfg(test)]
+pub(crate) fn hourly_test_document(prices: &[f64]) -> PriceDocument {
+    use chrono::TimeZone;
+    let start = Utc
+        .with_ymd_and_hms(2024, 1, 15, 0, 0, 0)
+        .single()
+        .expect("valid test date");
+    test_document(start, Resolution::PT60M, prices)
 }
 
 #[cfg(test)]
@@ -72,13 +1042,846 @@ mod tests {
         assert_eq!(Resolution::PT60M.minutes(), 60);
     }
 
+    #[test]
+    fn test_resolution_label() {
+        assert_eq!(Resolution::PT15M.label(), "15 minutes");
+        assert_eq!(Resolution::PT60M.label(), "1 hour");
+    }
+
     #[test]
     fn test_price_per_kwh() {
         use chrono::Utc;
         let price_point = PricePoint {
             timestamp: Utc::now(),
-            price: 50.0,
+            price: price_from_f64(50.0),
+            quality: None,
+        };
+        assert!((price_to_f64(price_point.price_per_kwh()) - 0.05).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn test_price_in_units() {
+        let point = PricePoint {
+            timestamp: Utc::now(),
+            price: price_from_f64(42.5),
+            quality: None,
+        };
+        assert!((price_to_f64(point.price_per_mwh()) - 42.5).abs() < f64::EPSILON);
+        assert!((price_to_f64(point.price_in(PriceUnit::PerMwh)) - 42.5).abs() < f64::EPSILON);
+        assert!((price_to_f64(point.price_in(PriceUnit::PerKwh)) - 0.0425).abs() < 0.000_001);
+
+        let negative = PricePoint {
+            timestamp: Utc::now(),
+            price: price_from_f64(-12.0),
+            quality: None,
+        };
+        assert!((price_to_f64(negative.price_in(PriceUnit::PerMwh)) + 12.0).abs() < f64::EPSILON);
+        assert!((price_to_f64(negative.price_in(PriceUnit::PerKwh)) + 0.012).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn test_is_stale() {
+        use chrono::TimeZone;
+        let now = Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap();
+        let mut doc = hourly_test_document(&[10.0]);
+
+        assert_eq!(doc.is_stale(Duration::hours(6), now), None);
+
+        doc.created_at = Some(now - Duration::hours(2));
+        assert_eq!(doc.is_stale(Duration::hours(6), now), Some(false));
+
+        doc.created_at = Some(now - Duration::hours(7));
+        assert_eq!(doc.is_stale(Duration::hours(6), now), Some(true));
+    }
+
+    #[test]
+    fn test_validate_price_ranges() {
+        let mut doc = hourly_test_document(&[-20.0, 50.0, 4500.0]);
+        assert!(doc.validate_price_ranges().is_empty());
+
+        doc.min_price_range = Some(price_from_f64(-10.0));
+        doc.max_price_range = Some(price_from_f64(4000.0));
+        let flagged: Vec<f64> = doc
+            .validate_price_ranges()
+            .iter()
+            .map(|p| price_to_f64(p.price))
+            .collect();
+        assert_eq!(flagged, vec![-20.0, 4500.0]);
+    }
+
+    #[test]
+    fn test_price_at_instant() {
+        let doc = hourly_test_document(&[10.0, 20.0, 30.0]);
+        let start = doc.period_start;
+
+        assert_eq!(doc.price_at_instant(start), Some(price_from_f64(10.0)));
+        assert_eq!(
+            doc.price_at_instant(start + Duration::minutes(90)),
+            Some(price_from_f64(20.0))
+        );
+        assert_eq!(
+            doc.price_at_instant(start + Duration::minutes(179)),
+            Some(price_from_f64(30.0))
+        );
+        assert_eq!(doc.price_at_instant(start - Duration::seconds(1)), None);
+        assert_eq!(doc.price_at_instant(doc.period_end), None);
+    }
+
+    #[test]
+    fn test_price_hour_correlation() {
+        let rising: Vec<f64> = (0..24).map(|h| 10.0 + f64::from(h) * 2.5).collect();
+        let doc = hourly_test_document(&rising);
+        let correlation = doc.price_hour_correlation(Tz::UTC).unwrap();
+        assert!((correlation - 1.0).abs() < 1e-9, "{correlation}");
+
+        let falling: Vec<f64> = rising.iter().rev().copied().collect();
+        let correlation = hourly_test_document(&falling)
+            .price_hour_correlation(Tz::UTC)
+            .unwrap();
+        assert!((correlation + 1.0).abs() < 1e-9, "{correlation}");
+
+        assert_eq!(
+            hourly_test_document(&[5.0; 24]).price_hour_correlation(Tz::UTC),
+            None
+        );
+        assert_eq!(
+            hourly_test_document(&[5.0]).price_hour_correlation(Tz::UTC),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cost_constant_power_aligned() {
+        // 100, 200, 300 EUR/MWh = 0.1, 0.2, 0.3 EUR/kWh
+        let doc = hourly_test_document(&[100.0, 200.0, 300.0]);
+        let cost = doc
+            .cost_constant_power(doc.period_start, Duration::hours(3), 2.0)
+            .unwrap();
+        assert!((cost - 1.2).abs() < 1e-9, "{cost}");
+
+        assert_eq!(
+            doc.cost_constant_power(doc.period_start, Duration::hours(4), 2.0),
+            None
+        );
+        assert_eq!(
+            doc.cost_constant_power(
+                doc.period_start - Duration::minutes(1),
+                Duration::hours(1),
+                2.0
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cost_constant_power_misaligned() {
+        let doc = hourly_test_document(&[100.0, 200.0, 300.0]);
+        // 30 min at 0.1 + 60 min at 0.2 + 15 min at 0.3, at 2 kW
+        let start = doc.period_start + Duration::minutes(30);
+        let cost = doc
+            .cost_constant_power(start, Duration::minutes(105), 2.0)
+            .unwrap();
+        assert!((cost - (0.1 + 0.4 + 0.15)).abs() < 1e-9, "{cost}");
+    }
+
+    #[test]
+    fn test_is_now_cheap() {
+        let doc = hourly_test_document(&[10.0, 20.0, 30.0, 40.0, 50.0, 60.0, 70.0, 80.0]);
+        let start = doc.period_start;
+
+        // 25th percentile is 27.5, so only the first two hours are cheap
+        assert_eq!(
+            doc.is_now_cheap(start + Duration::minutes(150), 0.25),
+            Some(false)
+        );
+        assert_eq!(
+            doc.is_now_cheap(start + Duration::minutes(90), 0.25),
+            Some(true)
+        );
+        assert_eq!(
+            doc.is_now_cheap(start + Duration::hours(7), 0.25),
+            Some(false)
+        );
+        assert_eq!(doc.is_now_cheap(doc.period_end, 0.25), None);
+    }
+
+    #[test]
+    fn test_split_at_median() {
+        let prices = |points: &[&PricePoint]| {
+            points
+                .iter()
+                .map(|p| price_to_f64(p.price))
+                .collect::<Vec<_>>()
+        };
+
+        // Odd count: the median point itself is cheap
+        let doc = hourly_test_document(&[5.0, 1.0, 3.0]);
+        let (cheap, expensive) = doc.split_at_median();
+        assert_eq!(prices(&cheap), vec![1.0, 3.0]);
+        assert_eq!(prices(&expensive), vec![5.0]);
+
+        // Even count: median 2.5 sits between the halves
+        let doc = hourly_test_document(&[4.0, 1.0, 3.0, 2.0]);
+        let (cheap, expensive) = doc.split_at_median();
+        assert_eq!(prices(&cheap), vec![1.0, 2.0]);
+        assert_eq!(prices(&expensive), vec![4.0, 3.0]);
+        assert_eq!(expensive[0].timestamp, doc.prices[0].timestamp);
+
+        // Ties at the median all go to the cheap half
+        let doc = hourly_test_document(&[2.0, 5.0, 2.0, 2.0]);
+        let (cheap, expensive) = doc.split_at_median();
+        assert_eq!((cheap.len(), expensive.len()), (3, 1));
+
+        let empty = hourly_test_document(&[]);
+        assert_eq!(empty.split_at_median(), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    fn test_quantile_of_sorted() {
+        let sorted = [10.0, 20.0, 30.0, 40.0];
+        assert_eq!(quantile_of_sorted(&sorted, 0.0), Some(10.0));
+        assert_eq!(quantile_of_sorted(&sorted, 0.5), Some(25.0));
+        assert_eq!(quantile_of_sorted(&sorted, 1.0), Some(40.0));
+        assert_eq!(quantile_of_sorted(&sorted, 2.0), Some(40.0));
+        assert_eq!(quantile_of_sorted(&[], 0.5), None);
+    }
+
+    #[test]
+    fn test_convert_currency() {
+        let mut nok = hourly_test_document(&[1000.0, 500.0]);
+        nok.currency = "NOK".to_string();
+        nok.max_price_range = Some(price_from_f64(40_000.0));
+
+        let eur = nok.convert_currency("EUR", 0.085);
+
+        assert_eq!(eur.currency, "EUR");
+        let prices: Vec<f64> = eur.prices.iter().map(|p| price_to_f64(p.price)).collect();
+        assert!((prices[0] - 85.0).abs() < 1e-9);
+        assert!((prices[1] - 42.5).abs() < 1e-9);
+        assert!((price_to_f64(eur.max_price_range.unwrap()) - 3400.0).abs() < 1e-9);
+        assert_eq!(eur.prices[0].timestamp, nok.prices[0].timestamp);
+        assert_eq!(nok.currency, "NOK");
+    }
+
+    #[test]
+    fn test_to_influx_line_protocol() {
+        let doc = hourly_test_document(&[42.1, -3.0]);
+        assert_eq!(
+            doc.to_influx_line_protocol("spot_price", "FI"),
+            "spot_price,zone=FI price=42.1 1705276800000000000\n\
+             spot_price,zone=FI price=-3 1705280400000000000\n"
+        );
+
+        let escaped =
+            hourly_test_document(&[1.5]).to_influx_line_protocol("spot price", "IT North,=");
+        assert_eq!(
+            escaped,
+            "spot\\ price,zone=IT\\ North\\,\\= price=1.5 1705276800000000000\n"
+        );
+    }
+
+    #[test]
+    fn test_coefficient_of_variation() {
+        // Mean 5, population standard deviation 2
+        let doc = hourly_test_document(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]);
+        let cv = doc.coefficient_of_variation().unwrap();
+        assert!((cv - 0.4).abs() < 1e-12);
+
+        assert_eq!(
+            hourly_test_document(&[6.0, 6.0]).coefficient_of_variation(),
+            Some(0.0)
+        );
+        assert_eq!(
+            hourly_test_document(&[-3.0, 3.0]).coefficient_of_variation(),
+            None
+        );
+        assert_eq!(
+            hourly_test_document(&[-3.0, 1.0]).coefficient_of_variation(),
+            None
+        );
+        assert_eq!(hourly_test_document(&[]).coefficient_of_variation(), None);
+    }
+
+    #[test]
+    fn test_validate_flags_only_implausible_prices() {
+        let doc = hourly_test_document(&[42.0, -99_999.0, -12.5, 4000.0]);
+
+        let anomalies = doc.validate();
+
+        assert_eq!(
+            anomalies,
+            vec![PriceAnomaly::OutOfRange {
+                point: doc.prices[1].clone(),
+                reason: "-99999 EUR/MWh is below -500".to_string(),
+            }]
+        );
+
+        let strict = doc.validate_within(price_from_f64(-10.0), price_from_f64(100.0));
+        assert_eq!(strict.len(), 3);
+        let PriceAnomaly::OutOfRange { point, reason } = &strict[2];
+        assert_eq!(point, &doc.prices[3]);
+        assert_eq!(reason, "4000 EUR/MWh is above 100");
+    }
+
+    #[test]
+    fn test_resample_to_hourly() {
+        use chrono::TimeZone;
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let doc = test_document(
+            start,
+            Resolution::PT15M,
+            &[10.0, 20.0, 30.0, 40.0, 50.0, 70.0],
+        );
+
+        let hourly = doc.resample(Resolution::PT60M).unwrap();
+
+        assert_eq!(hourly.resolution, Resolution::PT60M);
+        assert_eq!(hourly.resolutions_seen, vec![Resolution::PT60M]);
+        assert_eq!(hourly.period_start, start);
+        assert_eq!(hourly.period_end, doc.period_end);
+        // The second hour only has two quarters, which are averaged as they are
+        assert_eq!(
+            hourly.prices,
+            vec![
+                PricePoint {
+                    timestamp: start,
+                    price: price_from_f64(25.0),
+                    quality: None,
+                },
+                PricePoint {
+                    timestamp: start + Duration::hours(1),
+                    price: price_from_f64(60.0),
+                    quality: None,
+                },
+            ]
+        );
+
+        assert_eq!(doc.resample(Resolution::PT15M).unwrap(), doc);
+    }
+
+    #[test]
+    fn test_resample_compressed_curve() {
+        use chrono::TimeZone;
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let doc = crate::parser::parse_day_ahead_prices(xml).unwrap();
+        let at = |h| Utc.with_ymd_and_hms(2025, 10, 12, h, 0, 0).unwrap();
+
+        let hourly = doc.resample(Resolution::PT60M).unwrap();
+
+        assert_eq!(hourly.prices.len(), 48);
+        let price = |h| price_to_f64(hourly.price_at_instant(at(h)).unwrap());
+        // 1.19 holds for three quarters before 0.43
+        assert!((price(9) - (1.19 * 3.0 + 0.43) / 4.0).abs() < 1e-9);
+        // 0 from 10:30 repeats through the left-out positions until 13:00
+        assert!(price(11).abs() < 1e-9);
+        assert!(price(12).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_resample_rejects_upsampling() {
+        let err = hourly_test_document(&[1.0, 2.0])
+            .resample(Resolution::PT15M)
+            .unwrap_err();
+        assert!(matches!(err, EntsoeError::InvalidResolution(_)));
+        assert!(err.to_string().contains("PT60M"));
+    }
+
+    #[test]
+    fn test_display_summary_and_table() {
+        let doc = hourly_test_document(&[42.1, -3.0, 105.5]);
+
+        let text = doc.to_string();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert_eq!(
+            lines[0],
+            "EUR PT60M 2024-01-15 00:00 UTC - 2024-01-15 03:00 UTC, 3 points, \
+             min -3.00 max 105.50 mean 48.20 EUR/MWh"
+        );
+        assert_eq!(
+            &lines[1..],
+            [
+                "2024-01-15 00:00 UTC   42.10",
+                "2024-01-15 01:00 UTC   -3.00",
+                "2024-01-15 02:00 UTC  105.50",
+            ]
+        );
+
+        let empty = hourly_test_document(&[]).to_string();
+        assert!(empty.ends_with(", 0 points"), "{empty}");
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let doc = hourly_test_document(&[2.67, -41.2]);
+        let mut out = Vec::new();
+        doc.write_csv(BiddingZone::FI, &mut out).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "timestamp,price_per_kwh,currency,price_area\n\
+             2024-01-15T00:00:00+00:00,0.00267,EUR,FI\n\
+             2024-01-15T01:00:00+00:00,-0.04120,EUR,FI\n"
+        );
+    }
+
+    #[test]
+    fn test_ramps() {
+        use chrono::TimeZone;
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let prices = [10.0, 20.0, 15.0];
+
+        let hourly = test_document(start, Resolution::PT60M, &prices).ramps();
+        assert_eq!(
+            hourly,
+            vec![
+                (start + Duration::hours(1), 10.0),
+                (start + Duration::hours(2), -5.0)
+            ]
+        );
+
+        // Same steps over a quarter of the time ramp four times as fast
+        let quarterly = test_document(start, Resolution::PT15M, &prices).ramps();
+        assert_eq!(
+            quarterly,
+            vec![
+                (start + Duration::minutes(15), 40.0),
+                (start + Duration::minutes(30), -20.0)
+            ]
+        );
+
+        assert!(hourly_test_document(&[10.0]).ramps().is_empty());
+    }
+
+    #[test]
+    fn test_iteration_and_len() {
+        let doc = hourly_test_document(&[10.0, 20.0, 30.0]);
+        assert_eq!(doc.len(), 3);
+        assert!(!doc.is_empty());
+        assert!(hourly_test_document(&[]).is_empty());
+
+        let prices: Vec<f64> = doc.iter().map(|p| price_to_f64(p.price)).collect();
+        assert_eq!(prices, vec![10.0, 20.0, 30.0]);
+
+        let mut total = 0.0;
+        for point in &doc {
+            total += price_to_f64(point.price);
+        }
+        assert!((total - 60.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_price_at() {
+        let doc = hourly_test_document(&[10.0, 20.0, 30.0]);
+        let start = doc.period_start;
+
+        // An instant exactly on a boundary belongs to the period that starts there
+        let point = doc.price_at(start + Duration::hours(1)).unwrap();
+        assert_eq!(point.timestamp, start + Duration::hours(1));
+        assert!((price_to_f64(point.price) - 20.0).abs() < f64::EPSILON);
+
+        assert!(doc.price_at(start - Duration::minutes(1)).is_none());
+    }
+
+    #[test]
+    fn test_last_point_end() {
+        let doc = hourly_test_document(&[10.0, 20.0]);
+        assert_eq!(doc.last_point_end(), Some(doc.period_end));
+        assert_eq!(hourly_test_document(&[]).last_point_end(), None);
+    }
+
+    #[test]
+    fn test_last_point_end_matches_fixture_period_end() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let doc = crate::parser::parse_day_ahead_prices(xml).unwrap();
+        assert_eq!(doc.last_point_end(), Some(doc.period_end));
+    }
+
+    #[test]
+    fn test_compressed_point_spans_left_out_positions() {
+        use chrono::TimeZone;
+        // Position 51 (10:30 UTC) is 0 and repeats until position 61 (13:00 UTC)
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let doc = crate::parser::parse_day_ahead_prices(xml).unwrap();
+        let at = |h, m| Utc.with_ymd_and_hms(2025, 10, 12, h, m, 0).unwrap();
+
+        let point = doc.price_at(at(11, 0)).unwrap();
+        assert_eq!(point.timestamp, at(10, 30));
+        assert_eq!(doc.price_at_instant(at(12, 59)), Some(Price::default()));
+        assert_eq!(doc.price_at(at(13, 0)).unwrap().timestamp, at(13, 0));
+        assert!(
+            doc.cost_constant_power(at(2, 0), Duration::hours(1), 1.0)
+                .is_some()
+        );
+
+        // Every quarter-hour counts once, so the weighted mean is the mean over all 192 periods
+        let periods = doc.filled_periods();
+        assert_eq!(periods.len(), 192);
+        let mean = periods.iter().map(|&(_, p)| price_to_f64(p)).sum::<f64>() / 192.0;
+        assert!((price_to_f64(doc.time_weighted_mean().unwrap()) - mean).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_point_end_mixed_resolution() {
+        // PT60M followed by PT15M: the last quarter-hour ends at period_end, not an hour later
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_mixed_resolution.xml");
+        let doc = crate::parser::parse_day_ahead_prices(xml).unwrap();
+        let last = doc.prices.last().unwrap();
+        assert_eq!(doc.last_point_end(), Some(doc.period_end));
+        assert_eq!(
+            doc.price_at(doc.period_end - Duration::minutes(1)),
+            Some(last)
+        );
+        assert_eq!(doc.price_at(doc.period_end), None);
+
+        // Windows count quarter-hours, the finest resolution, and end with the last point
+        let (start, end, _) = doc.cheapest_window(4).unwrap();
+        assert_eq!(end - start, Duration::hours(1));
+        assert!(end <= doc.period_end);
+    }
+
+    #[test]
+    fn test_cheapest_window() {
+        let doc = hourly_test_document(&[50.0, 10.0, 20.0, 80.0, 5.0, 90.0]);
+        let (start, end, avg) = doc.cheapest_window(2).unwrap();
+        assert_eq!(start, doc.prices[1].timestamp);
+        assert_eq!(end, doc.prices[3].timestamp);
+        assert!((price_to_f64(avg) - 15.0).abs() < 0.000_001);
+
+        let (start, end, avg) = doc.most_expensive_window(2).unwrap();
+        assert_eq!(start, doc.prices[2].timestamp);
+        assert_eq!(end, doc.prices[4].timestamp);
+        assert!((price_to_f64(avg) - 50.0).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn test_window_ties_pick_earliest() {
+        let doc = hourly_test_document(&[30.0, 10.0, 30.0, 10.0, 30.0]);
+        let (start, _, _) = doc.cheapest_window(1).unwrap();
+        assert_eq!(start, doc.prices[1].timestamp);
+        let (start, _, _) = doc.most_expensive_window(2).unwrap();
+        assert_eq!(start, doc.prices[0].timestamp);
+    }
+
+    #[test]
+    fn test_window_spans_gaps() {
+        // Position 3 (00:00) is missing and repeats the price at position 2
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_compact.xml");
+        let doc = crate::parser::parse_day_ahead_prices(xml).unwrap();
+        assert_eq!(doc.prices.len(), 3);
+
+        let (start, end, avg) = doc.cheapest_window(2).unwrap();
+        assert_eq!(start, doc.prices[1].timestamp);
+        assert_eq!(end - start, Duration::hours(2));
+        assert!((price_to_f64(avg) + 12.5).abs() < 0.000_001);
+
+        // The gap counts as a period, so three periods from 23:00 reach the last point
+        let (start, end, _) = doc.most_expensive_window(3).unwrap();
+        assert_eq!(start, doc.prices[1].timestamp);
+        assert_eq!(end, doc.period_end);
+        assert!(doc.cheapest_window(4).is_some());
+        assert!(doc.cheapest_window(5).is_none());
+    }
+
+    #[test]
+    fn test_window_longer_than_series() {
+        let doc = hourly_test_document(&[30.0, 10.0, 20.0]);
+        assert!(doc.cheapest_window(4).is_none());
+        assert!(doc.most_expensive_window(4).is_none());
+        assert!(doc.cheapest_window(0).is_none());
+        assert!(doc.cheapest_window(3).is_some());
+    }
+
+    #[test]
+    fn test_peak_avoidance_savings() {
+        use chrono::TimeZone;
+        // Local midnight in Helsinki (UTC+2 in winter), two full local days
+        let start = Utc.with_ymd_and_hms(2024, 1, 14, 22, 0, 0).unwrap();
+        let mut prices = vec![50.0; 24];
+        prices[18] = 150.0;
+        prices.extend(std::iter::repeat_n(20.0, 24));
+        prices[24 + 7] = 68.0;
+        let doc = test_document(start, Resolution::PT60M, &prices);
+
+        // Day one: peak 150, mean 54.1667; day two: peak 68, mean 22
+        let expected = 2.0 * ((150.0 - 1300.0 / 24.0) + (68.0 - 22.0)) / 1000.0;
+        let savings = doc.peak_avoidance_savings(chrono_tz::Europe::Helsinki, 2.0);
+        assert!((savings - expected).abs() < 1e-9, "{savings} != {expected}");
+
+        // In UTC the same points straddle three days
+        let utc_savings = doc.peak_avoidance_savings(chrono_tz::UTC, 2.0);
+        assert!((utc_savings - expected).abs() > 1e-6);
+        assert!(hourly_test_document(&[]).peak_avoidance_savings(chrono_tz::UTC, 2.0) == 0.0);
+    }
+
+    #[test]
+    fn test_peak_avoidance_savings_averages_quarters() {
+        use chrono::TimeZone;
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        // Hourly means 10 and 30: the 60 quarter alone is not the peak price
+        let doc = test_document(
+            start,
+            Resolution::PT15M,
+            &[0.0, 0.0, 0.0, 40.0, 60.0, 20.0, 20.0, 20.0],
+        );
+
+        let savings = doc.peak_avoidance_savings(chrono_tz::UTC, 1.0);
+        assert!((savings - 10.0 / 1000.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_windows_below_avg() {
+        let doc = hourly_test_document(&[50.0, 10.0, 20.0, 80.0, 5.0, 25.0, 90.0]);
+        let windows = doc.windows_below_avg(2, 30.0);
+
+        let starts: Vec<_> = windows.iter().map(|(start, _)| *start).collect();
+        assert_eq!(
+            starts,
+            vec![doc.prices[1].timestamp, doc.prices[4].timestamp,]
+        );
+        assert!((windows[0].1 - 15.0).abs() < 0.000_001);
+        assert!((windows[1].1 - 15.0).abs() < 0.000_001);
+
+        let windows = doc.windows_below_avg(1, 30.0);
+        assert_eq!(windows.len(), 4);
+        assert!(windows.windows(2).all(|w| w[0].0 < w[1].0));
+    }
+
+    #[test]
+    fn test_windows_below_avg_compressed_curve() {
+        use chrono::TimeZone;
+        // 0 from 10:30 repeats through the left-out positions until 13:00
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let doc = crate::parser::parse_day_ahead_prices(xml).unwrap();
+        let at = |h, m| Utc.with_ymd_and_hms(2025, 10, 12, h, m, 0).unwrap();
+
+        let windows: Vec<_> = doc
+            .windows_below_avg(4, 0.005)
+            .into_iter()
+            .filter(|&(start, _)| start < at(13, 0))
+            .collect();
+
+        // From 10:15 (0.01 then three zeros) to 12:00, the last window ending at 13:00
+        let starts: Vec<_> = windows.iter().map(|(start, _)| *start).collect();
+        let expected: Vec<_> = (0..8)
+            .map(|i| at(10, 15) + Duration::minutes(15 * i))
+            .collect();
+        assert_eq!(starts, expected);
+        assert!((windows[0].1 - 0.0025).abs() < 1e-9);
+        assert!(windows[1..].iter().all(|(_, avg)| avg.abs() < 1e-9));
+    }
+
+    #[test]
+    fn test_windows_below_avg_none_qualify() {
+        let doc = hourly_test_document(&[50.0, 40.0, 60.0]);
+        assert!(doc.windows_below_avg(2, 40.0).is_empty());
+        assert!(doc.windows_below_avg(4, 100.0).is_empty());
+        assert!(doc.windows_below_avg(0, 100.0).is_empty());
+    }
+
+    #[test]
+    fn test_flex_value_per_kwh() {
+        let doc = hourly_test_document(&[40.0, -10.0, 120.0, 60.0]);
+        assert!((price_to_f64(doc.flex_value_per_kwh().unwrap()) - 0.13).abs() < 0.000_001);
+        assert_eq!(hourly_test_document(&[]).flex_value_per_kwh(), None);
+    }
+
+    #[test]
+    fn test_window_empty_series() {
+        let doc = hourly_test_document(&[]);
+        assert!(doc.cheapest_window(1).is_none());
+        assert!(doc.most_expensive_window(1).is_none());
+    }
+
+    #[test]
+    fn test_bucket_means() {
+        use chrono::TimeZone;
+        // Local midnight in Helsinki (UTC+2 in winter), price equals the local hour
+        let start = Utc.with_ymd_and_hms(2024, 1, 14, 22, 0, 0).unwrap();
+        let prices: Vec<f64> = (0..24).map(f64::from).collect();
+        let doc = test_document(start, Resolution::PT60M, &prices);
+
+        let time = |h| NaiveTime::from_hms_opt(h, 0, 0).unwrap();
+        let buckets = vec![
+            ("night".to_string(), time(22), time(6)),
+            ("day".to_string(), time(6), time(22)),
+            ("all_day".to_string(), time(6), time(6)),
+        ];
+        let means = doc.bucket_means(chrono_tz::Europe::Helsinki, &buckets);
+
+        // Night covers 22, 23 and 0..=5; day covers 6..=21
+        assert!((price_to_f64(means["night"]) - 7.5).abs() < 0.000_001);
+        assert!((price_to_f64(means["day"]) - 13.5).abs() < 0.000_001);
+        // Equal start and end wraps all the way around
+        assert!((price_to_f64(means["all_day"]) - 11.5).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn test_flat_consumption_price_kwh() {
+        use chrono::TimeZone;
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        // One hour at 100, then four quarters averaging 50
+        let mut doc = test_document(start, Resolution::PT15M, &[20.0, 40.0, 60.0, 80.0]);
+        for point in &mut doc.prices {
+            point.timestamp += Duration::hours(1);
+        }
+        doc.prices.insert(
+            0,
+            PricePoint {
+                timestamp: start,
+                price: price_from_f64(100.0),
+                quality: None,
+            },
+        );
+        doc.resolutions_seen = vec![Resolution::PT60M, Resolution::PT15M];
+        doc.period_end = start + Duration::hours(2);
+
+        let weighted = price_to_f64(doc.time_weighted_mean().unwrap());
+        assert!((weighted - 75.0).abs() < 1e-9);
+        let flat = price_to_f64(doc.flat_consumption_price_kwh().unwrap());
+        assert!((flat - weighted / 1000.0).abs() < 1e-12);
+
+        assert_eq!(hourly_test_document(&[]).flat_consumption_price_kwh(), None);
+    }
+
+    #[test]
+    fn test_content_hash() {
+        let doc = hourly_test_document(&[10.0, 20.5, -3.0]);
+        let mut same = doc.clone();
+        same.prices = Vec::with_capacity(1000);
+        same.prices.extend(doc.prices.iter().rev().cloned());
+        same.created_at = Some(Utc::now());
+
+        assert_eq!(doc.content_hash(), same.content_hash());
+
+        let mut changed = doc.clone();
+        changed.prices[1].price = price_from_f64(20.6);
+        assert_ne!(doc.content_hash(), changed.content_hash());
+    }
+
+    #[test]
+    fn test_localized() {
+        let doc = hourly_test_document(&[10.0, 20.5]);
+
+        let local = doc.localized(chrono_tz::Europe::Helsinki);
+
+        assert_eq!(local.len(), 2);
+        // 00:00 UTC is 02:00 EET in January
+        assert_eq!(local[0].0.format("%H:%M %Z").to_string(), "02:00 EET");
+        assert_eq!(local[0].0, doc.prices[0].timestamp);
+        assert!((price_to_f64(local[1].1) - 20.5).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_to_columns() {
+        let doc = hourly_test_document(&[10.0, 20.5, -3.0]);
+
+        let (timestamps, prices) = doc.to_columns();
+
+        assert_eq!(timestamps.len(), doc.prices.len());
+        assert_eq!(prices.len(), doc.prices.len());
+        for ((timestamp, price), point) in timestamps.iter().zip(&prices).zip(&doc.prices) {
+            assert_eq!(*timestamp, point.timestamp);
+            assert!((price - price_to_f64(point.price)).abs() < f64::EPSILON);
+        }
+    }
+
+    #[cfg(feature = "arrow")]
+    #[test]
+    fn test_to_record_batch() {
+        use arrow::array::{Float64Array, TimestampMillisecondArray};
+
+        let doc = hourly_test_document(&[10.0, 20.5, -3.0]);
+
+        let batch = doc.to_record_batch().unwrap();
+
+        assert_eq!(batch.num_rows(), doc.prices.len());
+        let timestamps = batch
+            .column_by_name("timestamp")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<TimestampMillisecondArray>()
+            .unwrap();
+        let prices = batch
+            .column_by_name("price_eur_mwh")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Float64Array>()
+            .unwrap();
+        assert_eq!(timestamps.value(0), 1_705_276_800_000);
+        assert_eq!(timestamps.value(2) - timestamps.value(1), 3_600_000);
+        assert!((prices.value(1) - 20.5).abs() < f64::EPSILON);
+    }
+
+    #[cfg(feature = "ndarray")]
+    #[test]
+    fn test_to_ndarray() {
+        let doc = hourly_test_document(&[10.0, 20.5, -3.0]);
+
+        let (timestamps, prices) = doc.to_ndarray();
+
+        assert_eq!(timestamps.len(), 3);
+        assert_eq!(prices.len(), 3);
+        assert_eq!(timestamps[0], 1_705_276_800);
+        assert_eq!(timestamps[2] - timestamps[1], 3600);
+        assert!((prices[1] - 20.5).abs() < f64::EPSILON);
+        assert!((prices.sum() - 27.5).abs() < 1e-9);
+    }
+
+    #[cfg(feature = "tabled")]
+    #[test]
+    fn test_to_table_rows() {
+        let doc = hourly_test_document(&[10.0, 20.5, -3.0]);
+
+        let rows = doc.to_table_rows(chrono_tz::Europe::Helsinki);
+
+        assert_eq!(rows.len(), doc.len());
+        assert_eq!(rows[1].price.to_string(), "20.5");
+        assert_eq!(rows[0].time.to_string(), "2024-01-15 02:00:00 EET");
+        let table = tabled::Table::new(&rows).to_string();
+        assert!(table.contains("Time") && table.contains("-3"));
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "decimal")]
+mod decimal_tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_decimal_sums_are_exact() {
+        let start = Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap();
+        let tenth: Price = "0.1".parse().unwrap();
+        let prices: Vec<PricePoint> = (0..1000)
+            .map(|i| PricePoint {
+                timestamp: start + Duration::minutes(15 * i),
+                price: tenth,
+                quality: None,
+            })
+            .collect();
+
+        let total: Price = prices.iter().map(|p| p.price).sum();
+        assert_eq!(total, Price::from(100));
+
+        let doc = PriceDocument {
+            created_at: None,
+            currency: "EUR".to_string(),
+            resolution: Resolution::PT15M,
+            resolutions_seen: vec![Resolution::PT15M],
+            period_start: start,
+            period_end: start + Duration::minutes(15 * 1000),
+            min_price_range: None,
+            max_price_range: None,
+            curve_type: None,
+            business_type: None,
+            prices,
         };
-        assert!((price_point.price_per_kwh() - 0.05).abs() < 0.000001);
+        let (_, _, avg) = doc.cheapest_window(1000).unwrap();
+        assert_eq!(avg, tenth);
+        assert_eq!(
+            doc.prices[0].price_per_kwh(),
+            "0.0001".parse::<Price>().unwrap()
+        );
     }
 }
@@ -0,0 +1,141 @@
+use std::fmt;
+
+/// ENTSO-E production type (`psrType`) B-codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PsrType {
+    Biomass,
+    FossilBrownCoalLignite,
+    FossilCoalDerivedGas,
+    FossilGas,
+    FossilHardCoal,
+    FossilOil,
+    FossilOilShale,
+    FossilPeat,
+    Geothermal,
+    HydroPumpedStorage,
+    HydroRunOfRiver,
+    HydroWaterReservoir,
+    Marine,
+    Nuclear,
+    OtherRenewable,
+    Solar,
+    Waste,
+    WindOffshore,
+    WindOnshore,
+    Other,
+}
+
+impl PsrType {
+    /// Parses a production type from its B-code (e.g. `B16`).
+    #[must_use]
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "B01" => Some(PsrType::Biomass),
+            "B02" => Some(PsrType::FossilBrownCoalLignite),
+            "B03" => Some(PsrType::FossilCoalDerivedGas),
+            "B04" => Some(PsrType::FossilGas),
+            "B05" => Some(PsrType::FossilHardCoal),
+            "B06" => Some(PsrType::FossilOil),
+            "B07" => Some(PsrType::FossilOilShale),
+            "B08" => Some(PsrType::FossilPeat),
+            "B09" => Some(PsrType::Geothermal),
+            "B10" => Some(PsrType::HydroPumpedStorage),
+            "B11" => Some(PsrType::HydroRunOfRiver),
+            "B12" => Some(PsrType::HydroWaterReservoir),
+            "B13" => Some(PsrType::Marine),
+            "B14" => Some(PsrType::Nuclear),
+            "B15" => Some(PsrType::OtherRenewable),
+            "B16" => Some(PsrType::Solar),
+            "B17" => Some(PsrType::Waste),
+            "B18" => Some(PsrType::WindOffshore),
+            "B19" => Some(PsrType::WindOnshore),
+            "B20" => Some(PsrType::Other),
+            _ => None,
+        }
+    }
+
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            PsrType::Biomass => "B01",
+            PsrType::FossilBrownCoalLignite => "B02",
+            PsrType::FossilCoalDerivedGas => "B03",
+            PsrType::FossilGas => "B04",
+            PsrType::FossilHardCoal => "B05",
+            PsrType::FossilOil => "B06",
+            PsrType::FossilOilShale => "B07",
+            PsrType::FossilPeat => "B08",
+            PsrType::Geothermal => "B09",
+            PsrType::HydroPumpedStorage => "B10",
+            PsrType::HydroRunOfRiver => "B11",
+            PsrType::HydroWaterReservoir => "B12",
+            PsrType::Marine => "B13",
+            PsrType::Nuclear => "B14",
+            PsrType::OtherRenewable => "B15",
+            PsrType::Solar => "B16",
+            PsrType::Waste => "B17",
+            PsrType::WindOffshore => "B18",
+            PsrType::WindOnshore => "B19",
+            PsrType::Other => "B20",
+        }
+    }
+
+    /// Human-readable name as used on the Transparency Platform.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            PsrType::Biomass => "Biomass",
+            PsrType::FossilBrownCoalLignite => "Fossil Brown coal/Lignite",
+            PsrType::FossilCoalDerivedGas => "Fossil Coal-derived gas",
+            PsrType::FossilGas => "Fossil Gas",
+            PsrType::FossilHardCoal => "Fossil Hard coal",
+            PsrType::FossilOil => "Fossil Oil",
+            PsrType::FossilOilShale => "Fossil Oil shale",
+            PsrType::FossilPeat => "Fossil Peat",
+            PsrType::Geothermal => "Geothermal",
+            PsrType::HydroPumpedStorage => "Hydro Pumped Storage",
+            PsrType::HydroRunOfRiver => "Hydro Run-of-river and poundage",
+            PsrType::HydroWaterReservoir => "Hydro Water Reservoir",
+            PsrType::Marine => "Marine",
+            PsrType::Nuclear => "Nuclear",
+            PsrType::OtherRenewable => "Other renewable",
+            PsrType::Solar => "Solar",
+            PsrType::Waste => "Waste",
+            PsrType::WindOffshore => "Wind Offshore",
+            PsrType::WindOnshore => "Wind Onshore",
+            PsrType::Other => "Other",
+        }
+    }
+}
+
+impl fmt::Display for PsrType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name(), self.code())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_round_trip() {
+        for n in 1..=20 {
+            let code = format!("B{n:02}");
+            let psr_type = PsrType::from_code(&code).unwrap();
+            assert_eq!(psr_type.code(), code);
+        }
+        assert_eq!(PsrType::from_code("B99"), None);
+    }
+
+    #[test]
+    fn test_name_and_display() {
+        assert_eq!(PsrType::Solar.name(), "Solar");
+        assert_eq!(PsrType::from_code("B19").unwrap().name(), "Wind Onshore");
+        assert_eq!(PsrType::Nuclear.to_string(), "Nuclear (B14)");
+        assert_eq!(
+            PsrType::HydroRunOfRiver.to_string(),
+            "Hydro Run-of-river and poundage (B11)"
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! Typed request parameters for the ENTSO-E API.
+
+use std::fmt;
+
+use crate::bidding_zone::BiddingZone;
+
+/// ENTSO-E `processType`: which forecast horizon or data kind a series belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProcessType {
+    /// `A01`: day-ahead
+    DayAhead,
+    /// `A16`: realised
+    Realised,
+    /// `A18`: current (latest available)
+    Current,
+    /// `A33`: year-ahead
+    YearAhead,
+    /// `A40`: intraday
+    Intraday,
+}
+
+impl ProcessType {
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            ProcessType::DayAhead => "A01",
+            ProcessType::Realised => "A16",
+            ProcessType::Current => "A18",
+            ProcessType::YearAhead => "A33",
+            ProcessType::Intraday => "A40",
+        }
+    }
+
+    /// Human-readable name, e.g. "Day ahead".
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProcessType::DayAhead => "Day ahead",
+            ProcessType::Realised => "Realised",
+            ProcessType::Current => "Current",
+            ProcessType::YearAhead => "Year ahead",
+            ProcessType::Intraday => "Intraday",
+        }
+    }
+}
+
+impl fmt::Display for ProcessType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name(), self.code())
+    }
+}
+
+/// ENTSO-E `documentType`: which kind of document a query asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DocumentType {
+    /// `A11`: aggregated energy data, used for cross-border physical flows
+    PhysicalFlows,
+    /// `A44`: price document, used for day-ahead and intraday prices
+    DayAheadPrices,
+    /// `A65`: system total load
+    TotalLoad,
+    /// `A68`: installed generation capacity per type
+    InstalledCapacity,
+    /// `A71`: generation forecast
+    GenerationForecast,
+    /// `A75`: actual generation per production type
+    ActualGeneration,
+}
+
+impl DocumentType {
+    /// Every document type the client can query.
+    pub const ALL: [DocumentType; 6] = [
+        DocumentType::PhysicalFlows,
+        DocumentType::DayAheadPrices,
+        DocumentType::TotalLoad,
+        DocumentType::InstalledCapacity,
+        DocumentType::GenerationForecast,
+        DocumentType::ActualGeneration,
+    ];
+
+    #[must_use]
+    pub fn code(&self) -> &'static str {
+        match self {
+            DocumentType::PhysicalFlows => "A11",
+            DocumentType::DayAheadPrices => "A44",
+            DocumentType::TotalLoad => "A65",
+            DocumentType::InstalledCapacity => "A68",
+            DocumentType::GenerationForecast => "A71",
+            DocumentType::ActualGeneration => "A75",
+        }
+    }
+
+    /// Human-readable description, e.g. "Day-ahead prices".
+    #[must_use]
+    pub fn description(&self) -> &'static str {
+        match self {
+            DocumentType::PhysicalFlows => "Cross-border physical flows",
+            DocumentType::DayAheadPrices => "Day-ahead prices",
+            DocumentType::TotalLoad => "Total load",
+            DocumentType::InstalledCapacity => "Installed generation capacity",
+            DocumentType::GenerationForecast => "Generation forecast",
+            DocumentType::ActualGeneration => "Actual generation per production type",
+        }
+    }
+}
+
+impl fmt::Display for DocumentType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.description(), self.code())
+    }
+}
+
+/// An area identified by its EIC code, for queries that also accept areas other than bidding
+/// zones, such as control areas, countries or whole regions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Domain {
+    eic_code: String,
+}
+
+impl Domain {
+    /// Wraps an EIC area code such as `10Y1001A1001A91G`.
+    ///
+    /// Returns `None` unless the code is 16 characters of uppercase letters, digits and `-`.
+    #[must_use]
+    pub fn from_eic(code: &str) -> Option<Self> {
+        let valid = code.len() == 16
+            && code
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-');
+        valid.then(|| Domain {
+            eic_code: code.to_string(),
+        })
+    }
+
+    #[must_use]
+    pub fn eic_code(&self) -> &str {
+        &self.eic_code
+    }
+}
+
+impl From<BiddingZone> for Domain {
+    fn from(zone: BiddingZone) -> Self {
+        Domain {
+            eic_code: zone.eic_code().to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Domain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.eic_code)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_type_codes() {
+        assert_eq!(ProcessType::DayAhead.code(), "A01");
+        assert_eq!(ProcessType::Realised.code(), "A16");
+        assert_eq!(ProcessType::Current.code(), "A18");
+        assert_eq!(ProcessType::YearAhead.code(), "A33");
+        assert_eq!(ProcessType::Intraday.code(), "A40");
+    }
+
+    #[test]
+    fn test_process_type_display() {
+        assert_eq!(ProcessType::DayAhead.to_string(), "Day ahead (A01)");
+        assert_eq!(ProcessType::Realised.to_string(), "Realised (A16)");
+    }
+
+    #[test]
+    fn test_document_type_codes() {
+        assert_eq!(DocumentType::DayAheadPrices.code(), "A44");
+        assert_eq!(DocumentType::PhysicalFlows.code(), "A11");
+        assert_eq!(DocumentType::ActualGeneration.code(), "A75");
+        assert_eq!(
+            DocumentType::DayAheadPrices.to_string(),
+            "Day-ahead prices (A44)"
+        );
+
+        let codes: std::collections::HashSet<_> =
+            DocumentType::ALL.iter().map(DocumentType::code).collect();
+        assert_eq!(codes.len(), DocumentType::ALL.len());
+    }
+
+    #[test]
+    fn test_domain_from_eic() {
+        let nordic = Domain::from_eic("10Y1001A1001A91G").unwrap();
+        assert_eq!(nordic.eic_code(), "10Y1001A1001A91G");
+        assert_eq!(
+            Domain::from(BiddingZone::FI),
+            Domain::from_eic("10YFI-1--------U").unwrap()
+        );
+
+        assert_eq!(Domain::from_eic("10YFI-1"), None);
+        assert_eq!(Domain::from_eic("10yfi-1--------u"), None);
+        assert_eq!(Domain::from_eic("10YFI-1--------U&x=1"), None);
+    }
+}
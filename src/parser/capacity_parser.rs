@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use crate::bidding_zone::BiddingZone;
+use crate::error::{EntsoeError, Result};
+use crate::models::capacity::InstalledCapacityDocument;
+use crate::models::psr_type::PsrType;
+use crate::parser::time_series::{MEGAWATT, measure_unit, parse_time_series};
+
+/// Parses an ENTSO-E installed capacity (documentType A68) response.
+///
+/// The response carries one yearly point per production type. Series with unknown
+/// production types are skipped.
+///
+/// # Errors
+///
+/// Returns error if XML is malformed or no capacities are present.
+pub fn parse_installed_capacity(
+    xml: &[u8],
+    zone: BiddingZone,
+    year: i32,
+) -> Result<InstalledCapacityDocument> {
+    let series = parse_time_series::<f64>(xml, "quantity")?.series;
+    let measure_unit = measure_unit(&series, Some(MEGAWATT))?;
+
+    let mut by_type = HashMap::new();
+    for raw in &series {
+        let Some(psr_type) = raw.psr_type.as_deref().and_then(PsrType::from_code) else {
+            continue;
+        };
+        if let Some(&(_, quantity, _)) = raw.periods.iter().flat_map(|p| &p.points).next() {
+            by_type.insert(psr_type, quantity);
+        }
+    }
+
+    if by_type.is_empty() {
+        return Err(EntsoeError::XmlParseError(
+            "No installed capacities found".to_string(),
+        ));
+    }
+
+    Ok(InstalledCapacityDocument {
+        zone,
+        year,
+        measure_unit,
+        by_type,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_installed_capacity_fixture() {
+        let xml = include_bytes!("../../tests/fixtures/installed_capacity_fi.xml");
+        let doc = parse_installed_capacity(xml, BiddingZone::FI, 2025).unwrap();
+
+        assert_eq!(doc.zone, BiddingZone::FI);
+        assert_eq!(doc.year, 2025);
+        assert_eq!(doc.by_type.len(), 4);
+        assert!((doc.by_type[&PsrType::Nuclear] - 4394.0).abs() < 0.000_001);
+        assert!((doc.by_type[&PsrType::WindOnshore] - 8310.0).abs() < 0.000_001);
+        assert!(!doc.by_type.contains_key(&PsrType::FossilGas));
+    }
+}
@@ -0,0 +1,74 @@
+use crate::error::{EntsoeError, Result};
+use crate::models::flow::{FlowDocument, FlowPoint};
+use crate::parser::time_series::{
+    MEGAWATT, measure_unit, parse_time_series, period_bounds, resolutions_seen,
+};
+
+/// Parses an ENTSO-E cross-border physical flow (documentType A11) response.
+///
+/// # Errors
+///
+/// Returns error if XML is malformed, required fields are missing, or no points are present.
+pub fn parse_physical_flows(xml: &[u8]) -> Result<FlowDocument> {
+    let series = parse_time_series::<f64>(xml, "quantity")?.series;
+
+    let resolution = *resolutions_seen(&series)
+        .first()
+        .ok_or_else(|| EntsoeError::MissingField("resolution".to_string()))?;
+    let (period_start, period_end) = period_bounds(&series);
+    let period_start =
+        period_start.ok_or_else(|| EntsoeError::MissingField("period start".to_string()))?;
+    let period_end =
+        period_end.ok_or_else(|| EntsoeError::MissingField("period end".to_string()))?;
+    let measure_unit = measure_unit(&series, Some(MEGAWATT))?;
+
+    let mut points: Vec<FlowPoint> = series
+        .iter()
+        .flat_map(|s| &s.periods)
+        .flat_map(|period| {
+            period
+                .points
+                .iter()
+                .filter_map(move |&(position, quantity, _)| {
+                    Some(FlowPoint {
+                        timestamp: period.timestamp(position, resolution)?,
+                        quantity_mw: quantity,
+                    })
+                })
+        })
+        .collect();
+
+    if points.is_empty() {
+        return Err(EntsoeError::XmlParseError(
+            "No flow points found".to_string(),
+        ));
+    }
+
+    points.sort_by_key(|p| p.timestamp);
+
+    Ok(FlowDocument {
+        resolution,
+        period_start,
+        period_end,
+        measure_unit,
+        points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::price::Resolution;
+
+    #[test]
+    fn test_parse_physical_flows_fixture() {
+        let xml = include_bytes!("../../tests/fixtures/physical_flows_fi_se1.xml");
+        let doc = parse_physical_flows(xml).unwrap();
+
+        assert_eq!(doc.resolution, Resolution::PT60M);
+        assert_eq!(doc.points.len(), 4);
+        assert!((doc.points[0].quantity_mw - 812.0).abs() < 0.000_001);
+        assert!((doc.points[3].quantity_mw - 1034.0).abs() < 0.000_001);
+        assert_eq!(doc.points[0].timestamp, doc.period_start);
+    }
+}
@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use crate::error::{EntsoeError, Result};
+use crate::models::generation::{
+    GenerationDocument, GenerationForecastDocument, GenerationPoint, GenerationSeries,
+};
+use crate::models::price::Resolution;
+use crate::models::psr_type::PsrType;
+use crate::parser::time_series::{
+    MEGAWATT, RawSeries, measure_unit, parse_time_series, period_bounds, resolutions_seen,
+};
+
+/// Parses an ENTSO-E generation forecast (documentType A71) response.
+///
+/// # Errors
+///
+/// Returns error if XML is malformed, required fields are missing, or no points are present.
+pub fn parse_generation_forecast(xml: &[u8]) -> Result<GenerationForecastDocument> {
+    let raw_series = parse_time_series::<f64>(xml, "quantity")?.series;
+
+    let resolution = *resolutions_seen(&raw_series)
+        .first()
+        .ok_or_else(|| EntsoeError::MissingField("resolution".to_string()))?;
+    let (period_start, period_end) = period_bounds(&raw_series);
+    let period_start =
+        period_start.ok_or_else(|| EntsoeError::MissingField("period start".to_string()))?;
+    let period_end =
+        period_end.ok_or_else(|| EntsoeError::MissingField("period end".to_string()))?;
+    let measure_unit = measure_unit(&raw_series, Some(MEGAWATT))?;
+
+    let series: Vec<GenerationSeries> = raw_series
+        .into_iter()
+        .map(|raw| {
+            let mut points = series_points(&raw, resolution);
+            points.sort_by_key(|p| p.timestamp);
+            GenerationSeries {
+                psr_type: raw.psr_type,
+                points,
+            }
+        })
+        .collect();
+
+    if series.iter().all(|s| s.points.is_empty()) {
+        return Err(EntsoeError::XmlParseError(
+            "No generation points found".to_string(),
+        ));
+    }
+
+    Ok(GenerationForecastDocument {
+        resolution,
+        period_start,
+        period_end,
+        measure_unit,
+        series,
+    })
+}
+
+/// Parses an ENTSO-E actual generation per production type (documentType A75) response.
+///
+/// The response carries one `<TimeSeries>` per production type, sometimes more when a type is
+/// split across several series; these are merged. Series with unknown production types are
+/// skipped, as are consumption series (those with an `outBiddingZone_Domain`, e.g. pumping by
+/// pumped storage), so each type holds generation only.
+///
+/// # Errors
+///
+/// Returns error if XML is malformed, required fields are missing, or no points are present.
+pub fn parse_actual_generation(xml: &[u8]) -> Result<GenerationDocument> {
+    let raw_series = parse_time_series::<f64>(xml, "quantity")?.series;
+
+    let resolution = *resolutions_seen(&raw_series)
+        .first()
+        .ok_or_else(|| EntsoeError::MissingField("resolution".to_string()))?;
+    let (period_start, period_end) = period_bounds(&raw_series);
+    let period_start =
+        period_start.ok_or_else(|| EntsoeError::MissingField("period start".to_string()))?;
+    let period_end =
+        period_end.ok_or_else(|| EntsoeError::MissingField("period end".to_string()))?;
+    let measure_unit = measure_unit(&raw_series, Some(MEGAWATT))?;
+
+    let mut by_type: HashMap<PsrType, Vec<GenerationPoint>> = HashMap::new();
+    for raw in raw_series.iter().filter(|raw| raw.out_domain.is_none()) {
+        let Some(psr_type) = raw.psr_type.as_deref().and_then(PsrType::from_code) else {
+            continue;
+        };
+        by_type
+            .entry(psr_type)
+            .or_default()
+            .extend(series_points(raw, resolution));
+    }
+    by_type.retain(|_, points| !points.is_empty());
+
+    if by_type.is_empty() {
+        return Err(EntsoeError::XmlParseError(
+            "No generation points found".to_string(),
+        ));
+    }
+
+    for points in by_type.values_mut() {
+        points.sort_by_key(|p| p.timestamp);
+    }
+
+    Ok(GenerationDocument {
+        resolution,
+        period_start,
+        period_end,
+        measure_unit,
+        by_type,
+    })
+}
+
+/// Resolves every point of a series to its timestamp, in document order.
+fn series_points(raw: &RawSeries<f64>, resolution: Resolution) -> Vec<GenerationPoint> {
+    raw.periods
+        .iter()
+        .flat_map(|period| {
+            period
+                .points
+                .iter()
+                .filter_map(move |&(position, quantity, _)| {
+                    Some(GenerationPoint {
+                        timestamp: period.timestamp(position, resolution)?,
+                        quantity_mw: quantity,
+                    })
+                })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_generation_forecast_fixture() {
+        let xml = include_bytes!("../../tests/fixtures/generation_forecast_fi.xml");
+        let doc = parse_generation_forecast(xml).unwrap();
+
+        assert_eq!(doc.resolution, Resolution::PT60M);
+        assert_eq!(doc.measure_unit.as_deref(), Some("MAW"));
+        assert_eq!(doc.series.len(), 2);
+        assert_eq!(doc.series[0].psr_type.as_deref(), Some("B16"));
+        assert_eq!(doc.series[1].psr_type.as_deref(), Some("B19"));
+        assert_eq!(doc.series[1].points.len(), 4);
+        assert!((doc.series[1].points[2].quantity_mw - 2310.0).abs() < 0.000_001);
+        assert_eq!(
+            doc.series[1].points[2].timestamp - doc.period_start,
+            chrono::Duration::hours(2)
+        );
+    }
+
+    #[test]
+    fn test_parse_actual_generation_fixture() {
+        let xml = include_bytes!("../../tests/fixtures/actual_generation_fi.xml");
+        let doc = parse_actual_generation(xml).unwrap();
+
+        assert_eq!(doc.resolution, Resolution::PT15M);
+        assert_eq!(doc.by_type.len(), 3);
+        assert_eq!(doc.by_type[&PsrType::Nuclear].len(), 4);
+        assert_eq!(doc.by_type[&PsrType::Solar].len(), 4);
+        // Wind onshore is split over two series and merged
+        let wind = &doc.by_type[&PsrType::WindOnshore];
+        assert_eq!(wind.len(), 8);
+        assert!(wind.windows(2).all(|w| w[0].timestamp < w[1].timestamp));
+        assert_eq!(wind[0].timestamp, doc.period_start);
+        assert!((wind[7].quantity_mw - 3120.0).abs() < 0.000_001);
+        assert!((doc.by_type[&PsrType::Nuclear][0].quantity_mw - 4371.0).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn test_parse_actual_generation_region() {
+        let xml = include_bytes!("../../tests/fixtures/actual_generation_nordic.xml");
+        let doc = parse_actual_generation(xml).unwrap();
+
+        assert_eq!(doc.resolution, Resolution::PT60M);
+        assert_eq!(doc.measure_unit.as_deref(), Some("MAW"));
+        assert_eq!(doc.by_type.len(), 5);
+        assert!(doc.by_type.values().all(|points| points.len() == 2));
+
+        let renewable: f64 = [
+            PsrType::HydroRunOfRiver,
+            PsrType::HydroWaterReservoir,
+            PsrType::Solar,
+            PsrType::WindOnshore,
+        ]
+        .iter()
+        .map(|psr| doc.by_type[psr][1].quantity_mw)
+        .sum();
+        assert!((renewable - 30_552.0).abs() < 0.000_001);
+        assert!((doc.by_type[&PsrType::Nuclear][0].quantity_mw - 11_320.0).abs() < 0.000_001);
+    }
+
+    #[test]
+    fn test_parse_actual_generation_skips_consumption() {
+        let xml = include_bytes!("../../tests/fixtures/actual_generation_pumped_storage.xml");
+        let doc = parse_actual_generation(xml).unwrap();
+
+        assert_eq!(doc.by_type.len(), 2);
+        // Pumping (the out-domain B10 series) is not mixed into pumped storage generation
+        let quantities: Vec<f64> = doc.by_type[&PsrType::HydroPumpedStorage]
+            .iter()
+            .map(|p| p.quantity_mw)
+            .collect();
+        assert_eq!(quantities, vec![412.0, 538.0]);
+        assert_eq!(doc.by_type[&PsrType::HydroRunOfRiver].len(), 2);
+    }
+
+    #[test]
+    fn test_parse_generation_forecast_without_points() {
+        let xml = b"<?xml version=\"1.0\"?><GL_MarketDocument></GL_MarketDocument>";
+        assert!(parse_generation_forecast(xml).is_err());
+    }
+}
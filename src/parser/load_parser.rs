@@ -0,0 +1,87 @@
+use crate::error::{EntsoeError, Result};
+use crate::models::load::{LoadDocument, LoadPoint};
+use crate::parser::time_series::{
+    MEGAWATT, measure_unit, parse_time_series, period_bounds, resolutions_seen,
+};
+
+/// Parses an ENTSO-E total load (documentType A65) response.
+///
+/// # Errors
+///
+/// Returns error if XML is malformed, required fields are missing, or no points are present.
+pub fn parse_total_load(xml: &[u8]) -> Result<LoadDocument> {
+    let series = parse_time_series::<f64>(xml, "quantity")?.series;
+
+    let resolution = *resolutions_seen(&series)
+        .first()
+        .ok_or_else(|| EntsoeError::MissingField("resolution".to_string()))?;
+    let (period_start, period_end) = period_bounds(&series);
+    let period_start =
+        period_start.ok_or_else(|| EntsoeError::MissingField("period start".to_string()))?;
+    let period_end =
+        period_end.ok_or_else(|| EntsoeError::MissingField("period end".to_string()))?;
+    let measure_unit = measure_unit(&series, Some(MEGAWATT))?;
+
+    let mut points: Vec<LoadPoint> = series
+        .iter()
+        .flat_map(|s| &s.periods)
+        .flat_map(|period| {
+            period
+                .points
+                .iter()
+                .filter_map(move |&(position, quantity, _)| {
+                    Some(LoadPoint {
+                        timestamp: period.timestamp(position, resolution)?,
+                        quantity_mw: quantity,
+                    })
+                })
+        })
+        .collect();
+
+    if points.is_empty() {
+        return Err(EntsoeError::XmlParseError(
+            "No load points found".to_string(),
+        ));
+    }
+
+    points.sort_by_key(|p| p.timestamp);
+
+    Ok(LoadDocument {
+        resolution,
+        period_start,
+        period_end,
+        measure_unit,
+        points,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::price::Resolution;
+
+    #[test]
+    fn test_parse_total_load_fixture() {
+        let xml = include_bytes!("../../tests/fixtures/total_load_fi.xml");
+        let doc = parse_total_load(xml).unwrap();
+
+        assert_eq!(doc.resolution, Resolution::PT60M);
+        assert_eq!(doc.measure_unit.as_deref(), Some("MAW"));
+        assert_eq!(doc.points.len(), 6);
+        assert!((doc.points[0].quantity_mw - 9180.0).abs() < 0.000_001);
+        assert!((doc.points[5].quantity_mw - 8876.0).abs() < 0.000_001);
+        assert_eq!(
+            doc.points[5].timestamp,
+            doc.period_end - chrono::Duration::hours(1)
+        );
+    }
+
+    #[test]
+    fn test_parse_total_load_rejects_other_unit() {
+        let xml = include_bytes!("../../tests/fixtures/total_load_unit_kwt.xml");
+        assert!(matches!(
+            parse_total_load(xml),
+            Err(EntsoeError::XmlParseError(_))
+        ));
+    }
+}
@@ -1,3 +1,16 @@
+pub mod capacity_parser;
+pub mod flow_parser;
+pub mod generation_parser;
+pub mod load_parser;
 pub mod price_parser;
+pub(crate) mod time_series;
 
-pub use price_parser::parse_day_ahead_prices;
+pub use capacity_parser::parse_installed_capacity;
+pub use flow_parser::parse_physical_flows;
+pub use generation_parser::{parse_actual_generation, parse_generation_forecast};
+pub use load_parser::parse_total_load;
+pub use price_parser::{
+    ParseOptions, parse_day_ahead_prices, parse_day_ahead_prices_detailed,
+    parse_day_ahead_prices_multi, parse_day_ahead_prices_stream,
+    parse_day_ahead_prices_with_options,
+};
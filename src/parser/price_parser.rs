@@ -1,212 +1,229 @@
-use chrono::{DateTime, Duration, Utc};
-use quick_xml::Reader;
-use quick_xml::events::Event;
+use std::io::BufRead;
+
+use chrono::{DateTime, Utc};
 
 use crate::error::{EntsoeError, Result};
-use crate::models::price::{PriceDocument, PricePoint, Resolution};
+use crate::models::price::{DetailedPriceDocument, Price, PriceDocument, PricePoint};
+use crate::parser::time_series::{
+    RawPeriod, decode_time_series, parse_time_series, period_bounds, resolutions_seen,
+    split_documents,
+};
+
+/// Options controlling how a price document is validated and placed in time.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    /// Fail if a period holds fewer or more points than its time interval and
+    /// resolution imply. A03 periods, which leave out repeated prices, fail only if their
+    /// positions don't start at 1 or run past the interval.
+    pub strict: bool,
+    /// Shift each period's points so that its first present position starts at the period
+    /// start, instead of placing position `n` at `n - 1` resolutions after it.
+    pub anchor_to_first_position: bool,
+}
 
 /// Parses ENTSO-E XML response into a price document.
 ///
 /// # Errors
 ///
 /// Returns error if XML is malformed, required fields are missing, or datetime parsing fails.
+pub fn parse_day_ahead_prices(xml: &[u8]) -> Result<PriceDocument> {
+    parse_day_ahead_prices_with_options(xml, ParseOptions::default())
+}
+
+/// Parses ENTSO-E XML response into a price document using the given options.
 ///
-/// # Panics
+/// # Errors
 ///
-/// Panics if `period_start` or `period_end` comparisons fail (should not happen with valid UTC datetimes).
-pub fn parse_day_ahead_prices(xml: &[u8]) -> Result<PriceDocument> {
-    let mut reader = Reader::from_reader(xml);
-    reader.config_mut().trim_text(true);
-
-    let mut is_error_document = false;
-    let mut error_code = None;
-    let mut error_text = None;
-
-    let mut currency = None;
-    let mut resolution = None;
-    let mut period_start = None;
-    let mut period_end = None;
-    let mut all_points: Vec<(DateTime<Utc>, u32, f64)> = Vec::new();
-
-    let mut in_time_series = false;
-    let mut in_period = false;
-    let mut in_point = false;
-    let mut in_time_interval = false;
-
-    let mut current_period_start = None;
-    let mut current_position = None;
-    let mut current_price = None;
-
-    let mut current_tag = String::new();
-    let mut buf = Vec::new();
-
-    loop {
-        match reader.read_event_into(&mut buf) {
-            Ok(Event::Start(ref e)) => {
-                let name = e.name();
-                current_tag = String::from_utf8_lossy(name.as_ref()).to_string();
-
-                match name.as_ref() {
-                    b"Acknowledgement_MarketDocument" => is_error_document = true,
-                    b"TimeSeries" => in_time_series = true,
-                    b"Period" if in_time_series => {
-                        in_period = true;
-                        current_period_start = None;
-                    }
-                    b"Point" if in_period => in_point = true,
-                    b"timeInterval" if in_period => in_time_interval = true,
-                    _ => {}
-                }
-            }
-            Ok(Event::End(ref e)) => {
-                let name = e.name();
-                match name.as_ref() {
-                    b"TimeSeries" => in_time_series = false,
-                    b"Period" => {
-                        in_period = false;
-                        in_time_interval = false;
-                    }
-                    b"Point" => {
-                        if let (Some(pos), Some(price), Some(start)) =
-                            (current_position, current_price, current_period_start)
-                        {
-                            all_points.push((start, pos, price));
-                        }
-                        current_position = None;
-                        current_price = None;
-                        in_point = false;
-                    }
-                    b"timeInterval" => in_time_interval = false,
-                    _ => {}
-                }
-                current_tag.clear();
-            }
-            Ok(Event::Text(e)) => {
-                let text = std::str::from_utf8(&e)
-                    .map_err(|e| EntsoeError::XmlParseError(format!("Invalid UTF-8: {e}")))?;
-                let text = text.trim();
+/// Returns error if XML is malformed, required fields are missing, or datetime parsing fails.
+/// In strict mode, also returns [`EntsoeError::XmlParseError`] if a period's point count
+/// does not match its declared interval and resolution.
+pub fn parse_day_ahead_prices_with_options(
+    xml: &[u8],
+    options: ParseOptions,
+) -> Result<PriceDocument> {
+    Ok(parse_day_ahead_prices_detailed(xml, options)?.document)
+}
+
+/// Like [`parse_day_ahead_prices_with_options`], but also returns each point's `position` in
+/// the source XML, for tracing points back to the document.
+///
+/// # Errors
+///
+/// Same as [`parse_day_ahead_prices_with_options`].
+pub fn parse_day_ahead_prices_detailed(
+    xml: &[u8],
+    options: ParseOptions,
+) -> Result<DetailedPriceDocument> {
+    let document = parse_time_series::<Price>(xml, "price.amount")?;
+    let series = document.series;
 
-                if text.is_empty() {
+    // Day-ahead prices are always in EUR (ENTSO-E standard for all zones).
+    let currency = series
+        .iter()
+        .find_map(|s| s.currency.clone())
+        .unwrap_or_else(|| "EUR".to_string());
+    let resolutions_seen = resolutions_seen(&series);
+    let resolution = *resolutions_seen
+        .first()
+        .ok_or_else(|| EntsoeError::MissingField("resolution".to_string()))?;
+    let (period_start, period_end) = period_bounds(&series);
+    let period_start =
+        period_start.ok_or_else(|| EntsoeError::MissingField("period start".to_string()))?;
+    let period_end =
+        period_end.ok_or_else(|| EntsoeError::MissingField("period end".to_string()))?;
+
+    if options.strict {
+        for series in &series {
+            // A03 leaves out prices that repeat the previous one, so its points can't be counted
+            let compressed = series.curve_type.as_deref() == Some("A03");
+            for period in &series.periods {
+                let (Some(start), Some(end)) = (period.start, period.end) else {
+                    continue;
+                };
+                let minutes = period.resolution.unwrap_or(resolution).minutes();
+                let expected = (end - start).num_minutes() / minutes;
+                if compressed {
+                    check_compressed_positions(period, expected, start, end)?;
                     continue;
                 }
-
-                match current_tag.as_str() {
-                    "code" if is_error_document => {
-                        error_code = Some(text.to_string());
-                    }
-                    "text" if is_error_document => {
-                        error_text = Some(text.to_string());
-                    }
-                    "currency_Unit.name" if in_time_series => {
-                        if currency.is_none() {
-                            currency = Some(text.to_string());
-                        }
-                    }
-                    "resolution" if in_period => {
-                        if resolution.is_none() {
-                            resolution = Resolution::parse(text);
-                        }
-                    }
-                    "start" if in_time_interval && in_period => {
-                        let text_with_seconds =
-                            if !text.contains(':') || text.matches(':').count() == 1 {
-                                format!("{}:00", text.trim_end_matches('Z')) + "Z"
-                            } else {
-                                text.to_string()
-                            };
-                        let dt = DateTime::parse_from_rfc3339(&text_with_seconds)
-                            .ok()
-                            .map(|dt| dt.with_timezone(&Utc));
-                        current_period_start = dt;
-                        if let Some(dt_val) = dt
-                            && period_start.is_none_or(|ps| ps > dt_val)
-                        {
-                            period_start = Some(dt_val);
-                        }
-                    }
-                    "end" if in_time_interval && in_period => {
-                        let text_with_seconds =
-                            if !text.contains(':') || text.matches(':').count() == 1 {
-                                format!("{}:00", text.trim_end_matches('Z')) + "Z"
-                            } else {
-                                text.to_string()
-                            };
-                        let dt = DateTime::parse_from_rfc3339(&text_with_seconds)
-                            .ok()
-                            .map(|dt| dt.with_timezone(&Utc));
-                        if let Some(dt_val) = dt
-                            && period_end.is_none_or(|pe| pe < dt_val)
-                        {
-                            period_end = Some(dt_val);
-                        }
-                    }
-                    "position" if in_point => {
-                        current_position = text.parse().ok();
-                    }
-                    "price.amount" if in_point => {
-                        current_price = text.parse().ok();
-                    }
-                    _ => {}
+                let actual = period.points.len();
+                if i64::try_from(actual).ok() != Some(expected) {
+                    return Err(EntsoeError::XmlParseError(format!(
+                        "Period {start} - {end} has {actual} points, expected {expected} at PT{minutes}M"
+                    )));
                 }
             }
-            Ok(Event::Eof) => break,
-            Err(e) => {
-                return Err(EntsoeError::XmlParseError(format!(
-                    "XML parsing error: {e}"
-                )));
-            }
-            _ => {}
         }
-        buf.clear();
-    }
-
-    // Check if this is an error response (e.g., "No matching data found")
-    if is_error_document {
-        let code = error_code.unwrap_or_else(|| "unknown".to_string());
-        let text = error_text.unwrap_or_else(|| "No error message provided".to_string());
-        return Err(EntsoeError::ApiError(format!(
-            "API returned error (code {code}): {text}"
-        )));
     }
 
-    // Day-ahead prices are always in EUR (ENTSO-E standard for all zones).
-    let currency = currency.unwrap_or_else(|| "EUR".to_string());
-    let resolution =
-        resolution.ok_or_else(|| EntsoeError::MissingField("resolution".to_string()))?;
-    let period_start =
-        period_start.ok_or_else(|| EntsoeError::MissingField("period start".to_string()))?;
-    let period_end =
-        period_end.ok_or_else(|| EntsoeError::MissingField("period end".to_string()))?;
+    let mut points: Vec<(PricePoint, u32)> = series
+        .iter()
+        .flat_map(|s| &s.periods)
+        .flat_map(|period| {
+            let first = period.points.iter().map(|&(position, ..)| position).min();
+            let shift = match first {
+                Some(first) if options.anchor_to_first_position => first - 1,
+                _ => 0,
+            };
+            period
+                .points
+                .iter()
+                .filter_map(move |&(position, price, ref quality)| {
+                    let timestamp = period.timestamp(position - shift, resolution)?;
+                    Some((
+                        PricePoint {
+                            timestamp,
+                            price,
+                            quality: quality.clone(),
+                        },
+                        position,
+                    ))
+                })
+        })
+        .collect();
 
-    if all_points.is_empty() {
+    if points.is_empty() {
         return Err(EntsoeError::XmlParseError(
             "No price points found".to_string(),
         ));
     }
 
-    let mut prices: Vec<PricePoint> = all_points
-        .into_iter()
-        .map(|(start, position, price)| {
-            let offset_minutes = (i64::from(position) - 1) * resolution.minutes();
-            let timestamp = start + Duration::minutes(offset_minutes);
-            PricePoint { timestamp, price }
-        })
-        .collect();
+    points.sort_by_key(|(p, _)| p.timestamp);
+    let (prices, positions) = points.into_iter().unzip();
 
-    prices.sort_by_key(|p| p.timestamp);
-
-    Ok(PriceDocument {
+    let document = PriceDocument {
+        created_at: document.created_at,
         currency,
         resolution,
+        resolutions_seen,
         period_start,
         period_end,
+        min_price_range: series.iter().find_map(|s| s.min_price_range),
+        max_price_range: series.iter().find_map(|s| s.max_price_range),
+        curve_type: series.iter().find_map(|s| s.curve_type.clone()),
+        business_type: series.iter().find_map(|s| s.business_type.clone()),
         prices,
+    };
+    Ok(DetailedPriceDocument {
+        document,
+        positions,
     })
 }
 
+/// Checks that an A03 period expands to exactly `expected` positions: the first block must start
+/// at position 1 and no block may start past the end of the interval.
+fn check_compressed_positions<V>(
+    period: &RawPeriod<V>,
+    expected: i64,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<()> {
+    let positions = period.points.iter().map(|&(position, ..)| position);
+    let (first, last) = (positions.clone().min(), positions.max());
+    if first == Some(1) && last.is_some_and(|last| i64::from(last) <= expected) {
+        return Ok(());
+    }
+    Err(EntsoeError::XmlParseError(format!(
+        "Period {start} - {end} has A03 positions {} to {}, expected 1 to {expected}",
+        first.unwrap_or_default(),
+        last.unwrap_or_default()
+    )))
+}
+
+/// Parses a byte stream of concatenated ENTSO-E XML responses, one price document per
+/// top-level market document.
+///
+/// # Errors
+///
+/// Returns error if the stream is malformed or any of the documents fails to parse.
+pub fn parse_day_ahead_prices_multi(xml: &[u8]) -> Result<Vec<PriceDocument>> {
+    split_documents(xml)?
+        .into_iter()
+        .map(parse_day_ahead_prices)
+        .collect()
+}
+
+/// Parses an ENTSO-E price document from `reader`, passing each point to `sink` as soon as it
+/// is decoded instead of collecting them.
+///
+/// Points arrive in document order, which is not necessarily sorted by timestamp. Periods
+/// without a resolution use the first resolution seen so far; points that still cannot be
+/// placed in time are skipped.
+///
+/// # Errors
+///
+/// Returns error if XML is malformed or the document is an acknowledgement. Points decoded
+/// before the error have already been passed to `sink`.
+pub fn parse_day_ahead_prices_stream(
+    reader: impl BufRead,
+    mut sink: impl FnMut(PricePoint),
+) -> Result<()> {
+    let mut fallback_resolution = None;
+    decode_time_series::<_, Price>(
+        reader,
+        "price.amount",
+        |period, position, price, quality| {
+            if let Some(resolution) = period.resolution {
+                fallback_resolution.get_or_insert(resolution);
+            }
+            let timestamp =
+                fallback_resolution.and_then(|resolution| period.timestamp(position, resolution));
+            if let Some(timestamp) = timestamp {
+                sink(PricePoint {
+                    timestamp,
+                    price,
+                    quality,
+                });
+            }
+        },
+    )?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::price::Resolution;
 
     #[test]
     fn test_parse_day_ahead_prices_fixture() {
@@ -221,7 +238,266 @@ mod tests {
         assert!(!doc.prices.is_empty());
 
         let first_price = &doc.prices[0];
-        assert!(first_price.price > 0.0);
+        assert!(first_price.price > Price::from(0));
+    }
+
+    #[test]
+    fn test_parse_curve_and_business_type() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let doc = parse_day_ahead_prices(xml).unwrap();
+        assert_eq!(doc.curve_type.as_deref(), Some("A03"));
+        assert_eq!(doc.business_type.as_deref(), Some("A62"));
+
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_price_range.xml");
+        let doc = parse_day_ahead_prices(xml).unwrap();
+        assert_eq!(doc.curve_type.as_deref(), Some("A01"));
+    }
+
+    #[test]
+    fn test_parse_price_range() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_price_range.xml");
+        let doc = parse_day_ahead_prices(xml).unwrap();
+
+        assert_eq!(doc.min_price_range, Some(Price::from(-500)));
+        assert_eq!(doc.max_price_range, Some(Price::from(4000)));
+        assert!(doc.validate_price_ranges().is_empty());
+
+        let fixture = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let doc = parse_day_ahead_prices(fixture).unwrap();
+        assert_eq!(doc.min_price_range, None);
+        assert_eq!(doc.max_price_range, None);
+    }
+
+    #[test]
+    fn test_parse_mixed_resolutions() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_mixed_resolution.xml");
+        let doc = parse_day_ahead_prices(xml).unwrap();
+
+        assert_eq!(doc.resolution, Resolution::PT60M);
+        assert_eq!(
+            doc.resolutions_seen,
+            vec![Resolution::PT60M, Resolution::PT15M]
+        );
+        assert_eq!(doc.prices.len(), 6);
+
+        // Second period is decoded with its own PT15M resolution
+        let timestamps: Vec<String> = doc
+            .prices
+            .iter()
+            .map(|p| p.timestamp.format("%H:%M").to_string())
+            .collect();
+        assert_eq!(
+            timestamps,
+            vec!["22:00", "23:00", "00:00", "00:15", "00:30", "00:45"]
+        );
+    }
+
+    #[test]
+    fn test_parse_uniform_resolution_seen_once() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let doc = parse_day_ahead_prices(xml).unwrap();
+        assert_eq!(doc.resolutions_seen, vec![Resolution::PT15M]);
+    }
+
+    #[test]
+    fn test_parse_concatenated_documents() {
+        let fi = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let mixed = include_bytes!("../../tests/fixtures/day_ahead_prices_mixed_resolution.xml");
+        let archive = [fi.as_slice(), b"\n", mixed.as_slice()].concat();
+
+        let docs = parse_day_ahead_prices_multi(&archive).unwrap();
+
+        assert_eq!(docs.len(), 2);
+        assert_eq!(docs[0], parse_day_ahead_prices(fi).unwrap());
+        assert_eq!(docs[1], parse_day_ahead_prices(mixed).unwrap());
+    }
+
+    #[test]
+    fn test_parse_missing_point_non_strict() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_missing_point.xml");
+        let doc = parse_day_ahead_prices(xml).unwrap();
+        assert_eq!(doc.prices.len(), 3);
+    }
+
+    #[test]
+    fn test_parse_missing_point_strict() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_missing_point.xml");
+        let err = parse_day_ahead_prices_with_options(
+            xml,
+            ParseOptions {
+                strict: true,
+                ..ParseOptions::default()
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, EntsoeError::XmlParseError(_)));
+        assert!(err.to_string().contains("has 3 points, expected 4"));
+
+        let complete = include_bytes!("../../tests/fixtures/day_ahead_prices_price_range.xml");
+        assert!(
+            parse_day_ahead_prices_with_options(
+                complete,
+                ParseOptions {
+                    strict: true,
+                    ..ParseOptions::default()
+                }
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_parse_compressed_strict() {
+        let strict = ParseOptions {
+            strict: true,
+            ..ParseOptions::default()
+        };
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        assert_eq!(
+            parse_day_ahead_prices_with_options(xml, strict).unwrap(),
+            parse_day_ahead_prices(xml).unwrap()
+        );
+
+        // A block past the end of the interval is still rejected
+        let xml = String::from_utf8_lossy(xml).replacen(
+            "<position>1</position>",
+            "<position>97</position>",
+            1,
+        );
+        let err = parse_day_ahead_prices_with_options(xml.as_bytes(), strict).unwrap_err();
+        assert!(err.to_string().contains("expected 1 to 96"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_anchor_to_first_position() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_offset_position.xml");
+        let times = |doc: PriceDocument| -> Vec<String> {
+            doc.prices
+                .iter()
+                .map(|p| p.timestamp.format("%H:%M").to_string())
+                .collect()
+        };
+
+        // By default position 3 lies two hours after the period start
+        let doc = parse_day_ahead_prices(xml).unwrap();
+        assert_eq!(doc.period_start.format("%H:%M").to_string(), "22:00");
+        assert_eq!(times(doc), vec!["00:00", "01:00"]);
+
+        let options = ParseOptions {
+            anchor_to_first_position: true,
+            ..ParseOptions::default()
+        };
+        let anchored = parse_day_ahead_prices_with_options(xml, options).unwrap();
+        assert_eq!(anchored.prices[0].timestamp, anchored.period_start);
+        assert_eq!(times(anchored), vec!["22:00", "23:00"]);
+
+        // Periods that start at position 1 are unaffected
+        let fi = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        assert_eq!(
+            parse_day_ahead_prices_with_options(fi, options).unwrap(),
+            parse_day_ahead_prices(fi).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_detailed_positions() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_price_range.xml");
+        let detailed = parse_day_ahead_prices_detailed(xml, ParseOptions::default()).unwrap();
+        assert_eq!(detailed.positions, vec![1, 2, 3, 4]);
+        assert_eq!(detailed.document, parse_day_ahead_prices(xml).unwrap());
+
+        // The A03 fixture omits repeated prices, so positions have gaps but still locate each
+        // point in its period
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let detailed = parse_day_ahead_prices_detailed(xml, ParseOptions::default()).unwrap();
+        let doc = &detailed.document;
+        assert_eq!(detailed.positions.len(), doc.prices.len());
+        assert_eq!(detailed.positions[0], 1);
+        let second_period = doc.period_start + chrono::Duration::days(1);
+        for (point, &position) in doc.prices.iter().zip(&detailed.positions) {
+            let period_start = if point.timestamp < second_period {
+                doc.period_start
+            } else {
+                second_period
+            };
+            let offset = (point.timestamp - period_start).num_minutes() / 15;
+            assert_eq!(i64::from(position), offset + 1);
+        }
+    }
+
+    #[test]
+    fn test_parse_point_quality() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_quality.xml");
+        let doc = parse_day_ahead_prices(xml).unwrap();
+        let qualities: Vec<_> = doc.prices.iter().map(|p| p.quality.as_deref()).collect();
+        assert_eq!(qualities, [None, None, Some("A03"), Some("A03")]);
+
+        let mut streamed = Vec::new();
+        parse_day_ahead_prices_stream(&xml[..], |point| streamed.push(point)).unwrap();
+        assert_eq!(streamed, doc.prices);
+
+        // Documents without markers leave every point's quality empty
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_price_range.xml");
+        let doc = parse_day_ahead_prices(xml).unwrap();
+        assert!(doc.prices.iter().all(|p| p.quality.is_none()));
+    }
+
+    #[test]
+    fn test_parse_namespace_prefixed_document() {
+        let prefixed = include_bytes!("../../tests/fixtures/day_ahead_prices_prefixed.xml");
+        let plain = include_bytes!("../../tests/fixtures/day_ahead_prices_price_range.xml");
+
+        let doc = parse_day_ahead_prices(prefixed).unwrap();
+
+        assert_eq!(doc.prices.len(), 4);
+        assert_eq!(doc, parse_day_ahead_prices(plain).unwrap());
+    }
+
+    #[test]
+    fn test_parse_with_byte_order_mark() {
+        let fi = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let with_bom = [b"\xEF\xBB\xBF".as_slice(), fi.as_slice()].concat();
+
+        assert_eq!(
+            parse_day_ahead_prices(&with_bom).unwrap(),
+            parse_day_ahead_prices(fi).unwrap()
+        );
+
+        let multi = parse_day_ahead_prices_multi(&with_bom).unwrap();
+        assert_eq!(multi.len(), 1);
+
+        let mut streamed = 0;
+        parse_day_ahead_prices_stream(with_bom.as_slice(), |_| streamed += 1).unwrap();
+        assert_eq!(streamed, parse_day_ahead_prices(fi).unwrap().prices.len());
+
+        // Single quotes and a standalone declaration don't matter either
+        let xml = std::str::from_utf8(fi).unwrap().replacen(
+            r#"<?xml version="1.0" encoding="utf-8"?>"#,
+            "\u{feff}<?xml version='1.0' encoding='UTF-8' standalone='yes'?>",
+            1,
+        );
+        assert!(xml.starts_with('\u{feff}'));
+        assert_eq!(
+            parse_day_ahead_prices(xml.as_bytes()).unwrap(),
+            parse_day_ahead_prices(fi).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_parse_stream_matches_collecting_parser() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_mixed_resolution.xml");
+        let mut streamed = Vec::new();
+
+        parse_day_ahead_prices_stream(xml.as_slice(), |point| streamed.push(point)).unwrap();
+
+        streamed.sort_by_key(|p| p.timestamp);
+        assert_eq!(streamed, parse_day_ahead_prices(xml).unwrap().prices);
+
+        let fi = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let mut count = 0;
+        parse_day_ahead_prices_stream(std::io::BufReader::new(fi.as_slice()), |_| count += 1)
+            .unwrap();
+        assert_eq!(count, parse_day_ahead_prices(fi).unwrap().len());
     }
 
     #[test]
@@ -230,4 +506,27 @@ mod tests {
         let result = parse_day_ahead_prices(xml);
         assert!(result.is_err());
     }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_parse_decimal_prices_match_source_text() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let doc = parse_day_ahead_prices(xml).unwrap();
+
+        let source = String::from_utf8_lossy(xml);
+        let amounts: Vec<&str> = source
+            .split("<price.amount>")
+            .skip(1)
+            .filter_map(|rest| rest.split('<').next())
+            .collect();
+
+        assert_eq!(doc.prices[0].price.to_string(), "2.67");
+        for point in &doc.prices {
+            assert!(
+                amounts.contains(&point.price.to_string().as_str()),
+                "{} not in source",
+                point.price
+            );
+        }
+    }
 }
@@ -0,0 +1,513 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, NaiveDateTime, Utc};
+use quick_xml::Reader;
+use quick_xml::events::Event;
+
+use crate::error::{EntsoeError, Result};
+use crate::models::price::Resolution;
+
+/// UTF-8 byte order mark, which files saved from browsers sometimes start with.
+const BYTE_ORDER_MARK: &[u8] = b"\xEF\xBB\xBF";
+
+/// Document-level fields plus every `<TimeSeries>` of an ENTSO-E market document.
+///
+/// `V` is the numeric type point values and price ranges are parsed into.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RawDocument<V = f64> {
+    pub created_at: Option<DateTime<Utc>>,
+    pub series: Vec<RawSeries<V>>,
+}
+
+/// A `<TimeSeries>` element as it appears in any ENTSO-E market document.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RawSeries<V = f64> {
+    pub currency: Option<String>,
+    pub measure_unit: Option<String>,
+    pub psr_type: Option<String>,
+    /// `outBiddingZone_Domain.mRID`, set on generation series that report consumption, such as
+    /// pumping by pumped storage.
+    pub out_domain: Option<String>,
+    pub curve_type: Option<String>,
+    pub business_type: Option<String>,
+    pub min_price_range: Option<V>,
+    pub max_price_range: Option<V>,
+    pub periods: Vec<RawPeriod<V>>,
+}
+
+/// A `<Period>` element with its points still keyed by position.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RawPeriod<V = f64> {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+    pub resolution: Option<Resolution>,
+    /// Position, value and `quality` marker of each point.
+    pub points: Vec<(u32, V, Option<String>)>,
+}
+
+impl<V> RawPeriod<V> {
+    /// Resolves a 1-based position to the start of its interval.
+    pub fn timestamp(
+        &self,
+        position: u32,
+        fallback_resolution: Resolution,
+    ) -> Option<DateTime<Utc>> {
+        let resolution = self.resolution.unwrap_or(fallback_resolution);
+        let offset_minutes = (i64::from(position) - 1) * resolution.minutes();
+        Some(self.start? + Duration::minutes(offset_minutes))
+    }
+}
+
+/// Earliest period start and latest period end across all series.
+pub(crate) fn period_bounds<V>(
+    series: &[RawSeries<V>],
+) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    let periods = series.iter().flat_map(|s| &s.periods);
+    let start = periods.clone().filter_map(|p| p.start).min();
+    let end = periods.filter_map(|p| p.end).max();
+    (start, end)
+}
+
+/// Distinct period resolutions in document order.
+pub(crate) fn resolutions_seen<V>(series: &[RawSeries<V>]) -> Vec<Resolution> {
+    let mut seen = Vec::new();
+    for res in series
+        .iter()
+        .flat_map(|s| &s.periods)
+        .filter_map(|p| p.resolution)
+    {
+        if !seen.contains(&res) {
+            seen.push(res);
+        }
+    }
+    seen
+}
+
+/// ENTSO-E unit code for megawatts, used by every quantity document this crate parses.
+pub(crate) const MEGAWATT: &str = "MAW";
+
+/// The quantity unit (`quantity_Measure_Unit.name`) shared by all series, if any declare one.
+///
+/// If `expected` is given, every declared unit must match it. Series without a unit are assumed
+/// to be in the expected unit.
+pub(crate) fn measure_unit<V>(
+    series: &[RawSeries<V>],
+    expected: Option<&str>,
+) -> Result<Option<String>> {
+    let mut unit: Option<&str> = None;
+    for found in series.iter().filter_map(|s| s.measure_unit.as_deref()) {
+        if let Some(expected) = expected.filter(|&expected| expected != found) {
+            return Err(EntsoeError::XmlParseError(format!(
+                "Unexpected quantity unit '{found}', expected '{expected}'"
+            )));
+        }
+        match unit {
+            Some(previous) if previous != found => {
+                return Err(EntsoeError::XmlParseError(format!(
+                    "Mixed quantity units '{previous}' and '{found}'"
+                )));
+            }
+            _ => unit = Some(found),
+        }
+    }
+    Ok(unit.map(str::to_string))
+}
+
+/// Decodes an ENTSO-E document and every `<TimeSeries>` in it, reading point values from `value_tag`
+/// (e.g. `price.amount` or `quantity`).
+///
+/// Acknowledgement documents (the API's way of saying "no data" or "bad request") are turned
+/// into `EntsoeError::Acknowledgement`.
+pub(crate) fn parse_time_series<V: FromStr + Default>(
+    xml: &[u8],
+    value_tag: &str,
+) -> Result<RawDocument<V>> {
+    decode_time_series(xml, value_tag, |period, position, value, quality| {
+        period.points.push((position, value, quality));
+    })
+}
+
+/// Like [`parse_time_series`], but hands each point to `on_point` as soon as it is decoded
+/// instead of storing it, so the returned periods only hold points `on_point` keeps.
+///
+/// `on_point` receives the enclosing period, whose interval and resolution are filled in as far
+/// as they precede the point in the document, followed by the point's position, value and
+/// `quality` marker.
+pub(crate) fn decode_time_series<R: BufRead, V: FromStr + Default>(
+    mut source: R,
+    value_tag: &str,
+    mut on_point: impl FnMut(&mut RawPeriod<V>, u32, V, Option<String>),
+) -> Result<RawDocument<V>> {
+    let has_bom = source
+        .fill_buf()
+        .map_err(|e| EntsoeError::XmlParseError(format!("Read error: {e}")))?
+        .starts_with(BYTE_ORDER_MARK);
+    if has_bom {
+        source.consume(BYTE_ORDER_MARK.len());
+    }
+
+    let mut reader = Reader::from_reader(source);
+    reader.config_mut().trim_text(true);
+
+    let mut is_error_document = false;
+    let mut error_code = None;
+    let mut error_text = None;
+
+    let mut created_at = None;
+    let mut series: Vec<RawSeries<V>> = Vec::new();
+
+    let mut in_time_series = false;
+    let mut in_period = false;
+    let mut in_point = false;
+    let mut in_time_interval = false;
+
+    let mut current_series = RawSeries::default();
+    let mut current_period = RawPeriod::default();
+    let mut current_position = None;
+    let mut current_value = None;
+    let mut current_quality = None;
+
+    let mut current_tag = String::new();
+    let mut buf = Vec::new();
+
+    loop {
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(ref e)) => {
+                // Local names, so documents with namespace prefixes (`ns:TimeSeries`) match too
+                let name = e.local_name();
+                current_tag = String::from_utf8_lossy(name.as_ref()).to_string();
+
+                match name.as_ref() {
+                    b"Acknowledgement_MarketDocument" => is_error_document = true,
+                    b"TimeSeries" => {
+                        in_time_series = true;
+                        current_series = RawSeries::default();
+                    }
+                    b"Period" if in_time_series => {
+                        in_period = true;
+                        current_period = RawPeriod::default();
+                    }
+                    b"Point" if in_period => in_point = true,
+                    b"timeInterval" if in_period => in_time_interval = true,
+                    _ => {}
+                }
+            }
+            Ok(Event::End(ref e)) => {
+                let name = e.local_name();
+                match name.as_ref() {
+                    b"TimeSeries" => {
+                        in_time_series = false;
+                        series.push(std::mem::take(&mut current_series));
+                    }
+                    b"Period" => {
+                        in_period = false;
+                        in_time_interval = false;
+                        current_series
+                            .periods
+                            .push(std::mem::take(&mut current_period));
+                    }
+                    b"Point" => {
+                        let quality = current_quality.take();
+                        if let (Some(pos), Some(value)) = (current_position, current_value.take()) {
+                            on_point(&mut current_period, pos, value, quality);
+                        }
+                        current_position = None;
+                        in_point = false;
+                    }
+                    b"timeInterval" => in_time_interval = false,
+                    _ => {}
+                }
+                current_tag.clear();
+            }
+            Ok(Event::Text(e)) => {
+                let text = std::str::from_utf8(&e)
+                    .map_err(|e| EntsoeError::XmlParseError(format!("Invalid UTF-8: {e}")))?;
+                let text = text.trim();
+
+                if text.is_empty() {
+                    continue;
+                }
+
+                match current_tag.as_str() {
+                    "code" if is_error_document => {
+                        error_code = Some(text.to_string());
+                    }
+                    "text" if is_error_document => {
+                        error_text = Some(text.to_string());
+                    }
+                    "createdDateTime" if !in_time_series => {
+                        created_at = parse_entsoe_timestamp(text).ok();
+                    }
+                    "currency_Unit.name" if in_time_series => {
+                        current_series.currency = Some(text.to_string());
+                    }
+                    "quantity_Measure_Unit.name" if in_time_series => {
+                        current_series.measure_unit = Some(text.to_string());
+                    }
+                    "outBiddingZone_Domain.mRID" if in_time_series => {
+                        current_series.out_domain = Some(text.to_string());
+                    }
+                    "psrType" if in_time_series => {
+                        current_series.psr_type = Some(text.to_string());
+                    }
+                    "curveType" if in_time_series => {
+                        current_series.curve_type = Some(text.to_string());
+                    }
+                    "businessType" if in_time_series => {
+                        current_series.business_type = Some(text.to_string());
+                    }
+                    "minPriceRange" if in_time_series => {
+                        current_series.min_price_range = text.parse().ok();
+                    }
+                    "maxPriceRange" if in_time_series => {
+                        current_series.max_price_range = text.parse().ok();
+                    }
+                    "resolution" if in_period => {
+                        current_period.resolution = Resolution::parse(text);
+                    }
+                    "start" if in_time_interval && in_period => {
+                        current_period.start = parse_entsoe_timestamp(text).ok();
+                    }
+                    "end" if in_time_interval && in_period => {
+                        current_period.end = parse_entsoe_timestamp(text).ok();
+                    }
+                    "position" if in_point => {
+                        current_position = text.parse().ok();
+                    }
+                    "quality" if in_point => {
+                        current_quality = Some(text.to_string());
+                    }
+                    tag if in_point && tag == value_tag => {
+                        current_value = text.parse().ok();
+                    }
+                    _ => {}
+                }
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(EntsoeError::XmlParseError(format!(
+                    "XML parsing error: {e}"
+                )));
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    // Check if this is an error response (e.g., "No matching data found")
+    if is_error_document {
+        let code = error_code.unwrap_or_else(|| "unknown".to_string());
+        let text = error_text.unwrap_or_else(|| "No error message provided".to_string());
+        return Err(EntsoeError::Acknowledgement { code, text });
+    }
+
+    Ok(RawDocument { created_at, series })
+}
+
+/// Splits a stream of concatenated XML documents into one slice per top-level element.
+pub(crate) fn split_documents(xml: &[u8]) -> Result<Vec<&[u8]>> {
+    let xml = xml.strip_prefix(BYTE_ORDER_MARK).unwrap_or(xml);
+    let mut reader = Reader::from_reader(xml);
+
+    let mut documents = Vec::new();
+    let mut depth = 0usize;
+    let mut document_start = 0;
+    let mut buf = Vec::new();
+
+    loop {
+        let event_start = offset(reader.buffer_position())?;
+        match reader.read_event_into(&mut buf) {
+            Ok(Event::Start(_)) => {
+                if depth == 0 {
+                    document_start = event_start;
+                }
+                depth += 1;
+            }
+            Ok(Event::End(_)) => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    let document_end = offset(reader.buffer_position())?;
+                    documents.push(&xml[document_start..document_end]);
+                }
+            }
+            Ok(Event::Empty(_)) if depth == 0 => {
+                let document_end = offset(reader.buffer_position())?;
+                documents.push(&xml[event_start..document_end]);
+            }
+            Ok(Event::Eof) => break,
+            Err(e) => {
+                return Err(EntsoeError::XmlParseError(format!(
+                    "XML parsing error: {e}"
+                )));
+            }
+            _ => {}
+        }
+        buf.clear();
+    }
+
+    Ok(documents)
+}
+
+fn offset(position: u64) -> Result<usize> {
+    usize::try_from(position)
+        .map_err(|_| EntsoeError::XmlParseError("Document too large".to_string()))
+}
+
+/// Parses an ENTSO-E timestamp into UTC.
+///
+/// Interval timestamps usually omit seconds (`2024-01-15T00:00Z`), while `createdDateTime` has
+/// them. Accepts hour, minute or second precision with an optional trailing `Z`, as well as
+/// full RFC 3339 with an offset. Surrounding whitespace is ignored.
+pub(crate) fn parse_entsoe_timestamp(text: &str) -> Result<DateTime<Utc>> {
+    let text = text.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(text) {
+        return Ok(dt.with_timezone(&Utc));
+    }
+
+    let naive = text.strip_suffix('Z').unwrap_or(text);
+    // chrono cannot parse a datetime without minutes, so pad hour precision
+    let naive = if naive.contains(':') {
+        naive.to_string()
+    } else {
+        format!("{naive}:00")
+    };
+    ["%Y-%m-%dT%H:%M:%S", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(&naive, format).ok())
+        .map(|dt| dt.and_utc())
+        .ok_or_else(|| EntsoeError::XmlParseError(format!("Invalid timestamp: '{text}'")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time_series_fixture() {
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let document = parse_time_series::<f64>(xml, "price.amount").unwrap();
+        let series = &document.series;
+
+        assert_eq!(
+            document.created_at.unwrap().to_rfc3339(),
+            "2025-10-12T16:38:43+00:00"
+        );
+
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].currency.as_deref(), Some("EUR"));
+        assert_eq!(series[0].periods.len(), 1);
+        assert_eq!(series[0].periods[0].resolution, Some(Resolution::PT15M));
+        assert_eq!(series[0].periods[0].points[0], (1, 2.67, None));
+    }
+
+    #[test]
+    fn test_prefixed_acknowledgement() {
+        let xml = br#"<ack:Acknowledgement_MarketDocument xmlns:ack="urn:iec62325.351:tc57wg16:451-1:acknowledgementdocument:7:0">
+            <ack:Reason><ack:code>999</ack:code><ack:text>No matching data found</ack:text></ack:Reason>
+        </ack:Acknowledgement_MarketDocument>"#;
+
+        let err = parse_time_series::<f64>(xml, "price.amount").unwrap_err();
+
+        assert!(matches!(
+            err,
+            EntsoeError::Acknowledgement { ref code, .. } if code == "999"
+        ));
+    }
+
+    #[test]
+    fn test_measure_unit_captured() {
+        let xml = include_bytes!("../../tests/fixtures/total_load_unit_kwt.xml");
+        let series = parse_time_series::<f64>(xml, "quantity").unwrap().series;
+
+        assert_eq!(series[0].measure_unit.as_deref(), Some("KWT"));
+        assert_eq!(measure_unit(&series, None).unwrap().as_deref(), Some("KWT"));
+        assert_eq!(
+            measure_unit(&series, Some("KWT")).unwrap().as_deref(),
+            Some("KWT")
+        );
+        assert!(matches!(
+            measure_unit(&series, Some(MEGAWATT)),
+            Err(EntsoeError::XmlParseError(_))
+        ));
+
+        let prices = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let series = parse_time_series::<f64>(prices, "price.amount")
+            .unwrap()
+            .series;
+        assert_eq!(measure_unit(&series, Some(MEGAWATT)).unwrap(), None);
+    }
+
+    #[test]
+    fn test_acknowledgement_document_is_error() {
+        let xml = b"<Acknowledgement_MarketDocument><Reason><code>999</code>\
+            <text>No matching data found</text></Reason></Acknowledgement_MarketDocument>";
+        let result = parse_time_series::<f64>(xml, "price.amount");
+        match result {
+            Err(EntsoeError::Acknowledgement { code, text }) => {
+                assert_eq!(code, "999");
+                assert_eq!(text, "No matching data found");
+            }
+            other => panic!("Expected Acknowledgement, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_entsoe_timestamp_minute_precision() {
+        let expected = "2024-01-15T23:00:00+00:00";
+        assert_eq!(
+            parse_entsoe_timestamp("2024-01-15T23:00Z")
+                .unwrap()
+                .to_rfc3339(),
+            expected
+        );
+        assert_eq!(
+            parse_entsoe_timestamp(" \n 2024-01-15T23:00Z\n  ")
+                .unwrap()
+                .to_rfc3339(),
+            expected
+        );
+        assert_eq!(
+            parse_entsoe_timestamp("2024-01-15T23Z")
+                .unwrap()
+                .to_rfc3339(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_parse_entsoe_timestamp_second_precision() {
+        assert_eq!(
+            parse_entsoe_timestamp("2025-10-12T16:38:43Z")
+                .unwrap()
+                .to_rfc3339(),
+            "2025-10-12T16:38:43+00:00"
+        );
+        assert_eq!(
+            parse_entsoe_timestamp("2025-10-12T18:38:43+02:00")
+                .unwrap()
+                .to_rfc3339(),
+            "2025-10-12T16:38:43+00:00"
+        );
+    }
+
+    #[test]
+    fn test_parse_entsoe_timestamp_malformed() {
+        for text in [
+            "",
+            "Z",
+            "2024-01-15",
+            "2024-13-01T00:00Z",
+            "yesterday",
+            "2024-01-15T00:00ZZ",
+        ] {
+            assert!(
+                matches!(
+                    parse_entsoe_timestamp(text),
+                    Err(EntsoeError::XmlParseError(_))
+                ),
+                "{text:?} should not parse"
+            );
+        }
+    }
+}
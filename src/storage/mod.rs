@@ -0,0 +1,27 @@
+//! Persistence of fetched price documents.
+//!
+//! [`PriceStore`] is the extension point for backends; with the default `storage` feature,
+//! `rusqlite::Connection` implements it using the `SQLite` schema of the `entsoe-*` binaries.
+
+use std::future::Future;
+
+use crate::bidding_zone::BiddingZone;
+use crate::error::Result;
+use crate::models::PriceDocument;
+
+#[cfg(feature = "storage")]
+mod sqlite;
+
+#[cfg(feature = "storage")]
+pub use sqlite::{init_schema, load_prices, store_price_document};
+
+/// A backend that persists price documents, e.g. a database.
+///
+/// The returned futures are not required to be `Send`, so connections that can't be shared
+/// between threads can implement it too.
+pub trait PriceStore {
+    /// Persists every point of `doc` under `zone` and returns the number of points stored.
+    ///
+    /// Storing the same document again should replace rather than duplicate its points.
+    fn store(&self, zone: BiddingZone, doc: &PriceDocument) -> impl Future<Output = Result<usize>>;
+}
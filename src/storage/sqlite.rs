@@ -0,0 +1,208 @@
+//! `SQLite` persistence for price documents, in the schema used by the `entsoe-*` binaries.
+//!
+//! Prices are stored one row per point and zone, in EUR/kWh as text with five decimals, keyed by
+//! RFC 3339 timestamp and zone code. Writes replace existing rows, so storing the same document
+//! twice is harmless.
+
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, params};
+
+use super::PriceStore;
+use crate::bidding_zone::BiddingZone;
+use crate::error::{EntsoeError, Result};
+use crate::models::price::{Price, PriceDocument, PricePoint};
+
+/// Creates the `prices` table and its indexes if they don't exist yet.
+///
+/// # Errors
+///
+/// Returns error if a statement fails.
+pub fn init_schema(conn: &Connection) -> rusqlite::Result<()> {
+    // CHECK constraints for data validation:
+    // - timestamp: RFC3339 format (YYYY-MM-DDTHH:MM:SS...)
+    // - price: Must be numeric (can be negative - electricity prices can go negative)
+    // - currency: Must be 3 uppercase letters (e.g., EUR, SEK)
+    // - price_area: Must be 2-8 characters (e.g., FI, NO2, IT-North)
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS prices (
+            timestamp TEXT NOT NULL CHECK(timestamp GLOB '[0-9][0-9][0-9][0-9]-[0-9][0-9]-[0-9][0-9]T[0-9][0-9]:[0-9][0-9]:[0-9][0-9]*'),
+            price TEXT NOT NULL CHECK(price GLOB '*[0-9]*' AND typeof(CAST(price AS REAL)) = 'real'),
+            currency TEXT NOT NULL CHECK(length(currency) = 3 AND currency = upper(currency)),
+            price_area TEXT NOT NULL CHECK(length(price_area) >= 2 AND length(price_area) <= 8),
+            PRIMARY KEY (timestamp, price_area)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_timestamp ON prices(timestamp)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_price_area ON prices(price_area)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Stores every point of `doc` under `zone`, replacing rows with the same timestamp and zone.
+///
+/// # Errors
+///
+/// Returns error if a statement fails, e.g. when the schema has not been created.
+pub fn store_price_document(
+    conn: &Connection,
+    zone: BiddingZone,
+    doc: &PriceDocument,
+) -> rusqlite::Result<()> {
+    let mut stmt = conn.prepare(
+        "INSERT OR REPLACE INTO prices (timestamp, price, currency, price_area)
+         VALUES (?1, ?2, ?3, ?4)",
+    )?;
+
+    let zone_code = zone.code();
+
+    for point in &doc.prices {
+        let timestamp = point.timestamp.to_rfc3339();
+        let price = format!("{:.5}", point.price_per_kwh());
+
+        stmt.execute(params![timestamp, price, &doc.currency, zone_code])?;
+    }
+
+    Ok(())
+}
+
+/// Stores documents with [`store_price_document`]; the schema must already exist.
+impl PriceStore for Connection {
+    async fn store(&self, zone: BiddingZone, doc: &PriceDocument) -> Result<usize> {
+        store_price_document(self, zone, doc).map_err(|e| EntsoeError::Storage(Box::new(e)))?;
+        Ok(doc.prices.len())
+    }
+}
+
+/// Loads the stored points for `zone` with `from <= timestamp < to`, in time order.
+///
+/// Prices are converted back to EUR/MWh, so they are rounded to 0.01 EUR/MWh.
+///
+/// # Errors
+///
+/// Returns error if the query fails or a stored timestamp or price cannot be parsed.
+pub fn load_prices(
+    conn: &Connection,
+    zone: BiddingZone,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+) -> rusqlite::Result<Vec<PricePoint>> {
+    let mut stmt = conn.prepare(
+        "SELECT timestamp, price FROM prices
+         WHERE price_area = ?1 AND timestamp >= ?2 AND timestamp < ?3
+         ORDER BY timestamp",
+    )?;
+
+    let rows = stmt.query_map(
+        params![zone.code(), from.to_rfc3339(), to.to_rfc3339()],
+        |row| {
+            let timestamp: String = row.get(0)?;
+            let price: String = row.get(1)?;
+            Ok((timestamp, price))
+        },
+    )?;
+
+    rows.map(|row| {
+        let (timestamp, price) = row?;
+        let timestamp = DateTime::parse_from_rfc3339(&timestamp)
+            .map_err(|e| conversion_error(0, e))?
+            .with_timezone(&Utc);
+        let price_per_kwh: Price = price.parse().map_err(|e| conversion_error(1, e))?;
+        Ok(PricePoint {
+            timestamp,
+            price: price_per_kwh * Price::from(1000),
+            quality: None,
+        })
+    })
+    .collect()
+}
+
+fn conversion_error(
+    column: usize,
+    error: impl std::error::Error + Send + Sync + 'static,
+) -> rusqlite::Error {
+    rusqlite::Error::FromSqlConversionFailure(column, rusqlite::types::Type::Text, Box::new(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::price::price_to_f64;
+    use chrono::{Duration, TimeZone};
+
+    #[test]
+    fn test_store_and_load_prices() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let doc = crate::parser::parse_day_ahead_prices(xml).unwrap();
+
+        store_price_document(&conn, BiddingZone::FI, &doc).unwrap();
+        // Storing again replaces rather than duplicates
+        store_price_document(&conn, BiddingZone::FI, &doc).unwrap();
+
+        let loaded = load_prices(&conn, BiddingZone::FI, doc.period_start, doc.period_end).unwrap();
+        assert_eq!(loaded.len(), doc.prices.len());
+        for (loaded, original) in loaded.iter().zip(&doc.prices) {
+            assert_eq!(loaded.timestamp, original.timestamp);
+            assert!((price_to_f64(loaded.price) - price_to_f64(original.price)).abs() < 0.01);
+        }
+
+        assert!(
+            load_prices(&conn, BiddingZone::SE3, doc.period_start, doc.period_end)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_load_prices_range_is_half_open() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_schema(&conn).unwrap();
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let doc = crate::parser::parse_day_ahead_prices(xml).unwrap();
+        store_price_document(&conn, BiddingZone::FI, &doc).unwrap();
+
+        let from = doc.prices[1].timestamp;
+        let to = doc.prices[3].timestamp;
+        let loaded = load_prices(&conn, BiddingZone::FI, from, to).unwrap();
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded[0].timestamp, from);
+        assert!(loaded[1].timestamp < to);
+
+        let before = Utc.with_ymd_and_hms(2000, 1, 1, 0, 0, 0).unwrap();
+        assert!(
+            load_prices(&conn, BiddingZone::FI, before, before + Duration::days(1))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_connection_price_store() {
+        let conn = Connection::open_in_memory().unwrap();
+        let xml = include_bytes!("../../tests/fixtures/day_ahead_prices_fi.xml");
+        let doc = crate::parser::parse_day_ahead_prices(xml).unwrap();
+
+        // Without the schema the insert fails
+        let err = conn.store(BiddingZone::FI, &doc).await.unwrap_err();
+        assert!(matches!(err, EntsoeError::Storage(_)));
+
+        init_schema(&conn).unwrap();
+        assert_eq!(
+            conn.store(BiddingZone::FI, &doc).await.unwrap(),
+            doc.prices.len()
+        );
+        let loaded = load_prices(&conn, BiddingZone::FI, doc.period_start, doc.period_end).unwrap();
+        assert_eq!(loaded.len(), doc.prices.len());
+    }
+}
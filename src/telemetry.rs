@@ -0,0 +1,128 @@
+//! Metrics recorded through the `metrics` crate when the `metrics` feature is enabled.
+//!
+//! Every metric carries an `endpoint` label, e.g. `day_ahead_prices`:
+//!
+//! - `entsoe_requests_total`: counter with an `outcome` label of `success` or `failure`
+//! - `entsoe_request_errors_total`: counter of failures with a `category` label from
+//!   [`error_category`]
+//! - `entsoe_request_duration_seconds`: histogram of the time spent per call, including retries
+
+use std::time::Duration;
+
+use crate::error::{EntsoeError, Result};
+
+pub(crate) const REQUESTS_TOTAL: &str = "entsoe_requests_total";
+pub(crate) const REQUEST_ERRORS_TOTAL: &str = "entsoe_request_errors_total";
+pub(crate) const REQUEST_DURATION_SECONDS: &str = "entsoe_request_duration_seconds";
+
+/// Records one finished call to `endpoint`.
+pub(crate) fn record_request<T>(endpoint: &'static str, elapsed: Duration, result: &Result<T>) {
+    let outcome = if result.is_ok() { "success" } else { "failure" };
+    metrics::counter!(REQUESTS_TOTAL, "endpoint" => endpoint, "outcome" => outcome).increment(1);
+    if let Err(e) = result {
+        metrics::counter!(REQUEST_ERRORS_TOTAL, "endpoint" => endpoint, "category" => error_category(e))
+            .increment(1);
+    }
+    metrics::histogram!(REQUEST_DURATION_SECONDS, "endpoint" => endpoint).record(elapsed);
+}
+
+/// Low-cardinality label for an error. [`EntsoeError::RetriesExhausted`] reports the category of
+/// its last error.
+pub(crate) fn error_category(error: &EntsoeError) -> &'static str {
+    match error {
+        EntsoeError::Http(e) if e.is_timeout() => "timeout",
+        EntsoeError::Http(_) => "http",
+        EntsoeError::Timeout => "timeout",
+        EntsoeError::ApiError { .. } => "api",
+        EntsoeError::Unauthorized { .. } => "unauthorized",
+        EntsoeError::RateLimited { .. } => "rate_limited",
+        EntsoeError::Acknowledgement { .. } => "acknowledgement",
+        EntsoeError::XmlParseError(_) | EntsoeError::MissingField(_) => "parse",
+        EntsoeError::InvalidBiddingZone(_)
+        | EntsoeError::InvalidTimeRange(_)
+        | EntsoeError::InvalidResolution(_) => "invalid_input",
+        EntsoeError::UrlError(_) | EntsoeError::InvalidHeader(_) => "config",
+        EntsoeError::Storage(_) => "storage",
+        EntsoeError::RetriesExhausted { last, .. } => error_category(last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{TimeZone, Utc};
+    use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+
+    use super::*;
+    use crate::{BiddingZone, EntsoeClient};
+
+    #[test]
+    fn test_success_increments_request_counter() {
+        let mut server = mockito::Server::new();
+        server
+            .mock("GET", "/api")
+            .match_query(mockito::Matcher::Any)
+            .with_body(include_str!("../tests/fixtures/day_ahead_prices_fi.xml"))
+            .create();
+        let client = EntsoeClient::builder("test-token")
+            .base_url(format!("{}/api", server.url()))
+            .build()
+            .unwrap();
+        let start = Utc.with_ymd_and_hms(2025, 10, 11, 22, 0, 0).unwrap();
+        let end = Utc.with_ymd_and_hms(2025, 10, 12, 22, 0, 0).unwrap();
+
+        // The local recorder is per thread, so drive the request on this one
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let recorder = DebuggingRecorder::new();
+        let snapshotter = recorder.snapshotter();
+        metrics::with_local_recorder(&recorder, || {
+            runtime
+                .block_on(client.fetch_day_ahead_prices(BiddingZone::FI, start, end))
+                .unwrap();
+        });
+
+        let snapshot = snapshotter.snapshot().into_vec();
+        let value = |name: &str| {
+            snapshot
+                .iter()
+                .find(|(key, ..)| key.key().name() == name)
+                .map(|(.., value)| value)
+        };
+        let requests = snapshot
+            .iter()
+            .find(|(key, ..)| key.key().name() == REQUESTS_TOTAL)
+            .unwrap();
+        let labels: Vec<_> = requests
+            .0
+            .key()
+            .labels()
+            .map(|l| (l.key(), l.value()))
+            .collect();
+        assert_eq!(
+            labels,
+            [("endpoint", "day_ahead_prices"), ("outcome", "success")]
+        );
+        assert_eq!(requests.3, DebugValue::Counter(1));
+        assert!(matches!(
+            value(REQUEST_DURATION_SECONDS),
+            Some(DebugValue::Histogram(samples)) if samples.len() == 1
+        ));
+        assert!(value(REQUEST_ERRORS_TOTAL).is_none());
+    }
+
+    #[test]
+    fn test_error_category() {
+        let exhausted = EntsoeError::RetriesExhausted {
+            attempts: 3,
+            last: Box::new(EntsoeError::RateLimited { url: String::new() }),
+        };
+        assert_eq!(error_category(&exhausted), "rate_limited");
+        assert_eq!(error_category(&EntsoeError::Timeout), "timeout");
+        assert_eq!(
+            error_category(&EntsoeError::InvalidTimeRange(String::new())),
+            "invalid_input"
+        );
+    }
+}
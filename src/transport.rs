@@ -0,0 +1,47 @@
+use std::future::Future;
+
+use bytes::Bytes;
+use reqwest::header::ACCEPT;
+use reqwest::{Client, StatusCode};
+use url::Url;
+
+use crate::error::Result;
+
+/// HTTP layer used by [`EntsoeClient`](crate::EntsoeClient).
+///
+/// The default [`ReqwestTransport`] talks to the network; tests and custom HTTP stacks can plug
+/// in their own implementation via [`EntsoeClient::with_transport`](crate::EntsoeClient::with_transport).
+pub trait HttpTransport: Send + Sync {
+    /// Performs a GET request and returns the status and body, whatever the status is.
+    fn get(&self, url: Url) -> impl Future<Output = Result<(StatusCode, Bytes)>> + Send;
+}
+
+/// Default transport backed by `reqwest`.
+pub struct ReqwestTransport {
+    client: Client,
+    accept: String,
+}
+
+impl ReqwestTransport {
+    /// Wraps a `reqwest` client, sending `accept` as the `Accept` header on every request.
+    pub fn new(client: Client, accept: impl Into<String>) -> Self {
+        Self {
+            client,
+            accept: accept.into(),
+        }
+    }
+}
+
+impl HttpTransport for ReqwestTransport {
+    async fn get(&self, url: Url) -> Result<(StatusCode, Bytes)> {
+        let response = self
+            .client
+            .get(url)
+            .header(ACCEPT, &self.accept)
+            .send()
+            .await?;
+        let status = response.status();
+        let body = response.bytes().await?;
+        Ok((status, body))
+    }
+}
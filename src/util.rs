@@ -0,0 +1,80 @@
+//! Small helpers for inspecting API responses.
+
+use quick_xml::events::Event;
+use quick_xml::{Reader, Writer};
+
+use crate::error::{EntsoeError, Result};
+
+/// Re-indents an XML document with two spaces per level, e.g. to make a raw API response
+/// readable.
+///
+/// Whitespace between elements is dropped and replaced by the indentation; text content is kept.
+///
+/// # Errors
+///
+/// Returns [`EntsoeError::XmlParseError`] if the input is not well-formed XML.
+pub fn pretty_print_xml(xml: &[u8]) -> Result<String> {
+    let mut reader = Reader::from_reader(xml);
+    reader.config_mut().trim_text(true);
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+
+    let mut depth = 0usize;
+    let mut buf = Vec::new();
+    loop {
+        let event = reader
+            .read_event_into(&mut buf)
+            .map_err(|e| EntsoeError::XmlParseError(format!("XML parsing error: {e}")))?;
+        match &event {
+            Event::Start(_) => depth += 1,
+            Event::End(_) => depth = depth.saturating_sub(1),
+            Event::Eof if depth > 0 => {
+                return Err(EntsoeError::XmlParseError(
+                    "XML parsing error: unexpected end of document".to_string(),
+                ));
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        writer
+            .write_event(event)
+            .map_err(|e| EntsoeError::XmlParseError(format!("XML writing error: {e}")))?;
+        buf.clear();
+    }
+
+    String::from_utf8(writer.into_inner())
+        .map_err(|e| EntsoeError::XmlParseError(format!("Invalid UTF-8: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pretty_print_compact_fixture() {
+        let xml = include_bytes!("../tests/fixtures/day_ahead_prices_compact.xml");
+
+        let pretty = pretty_print_xml(xml).unwrap();
+
+        assert!(pretty.lines().count() > 20, "{pretty}");
+        assert!(pretty.contains("\n  <mRID>"), "{pretty}");
+        assert!(
+            pretty.contains("\n    <Period>\n      <timeInterval>"),
+            "{pretty}"
+        );
+        assert_eq!(
+            crate::parser::parse_day_ahead_prices(pretty.as_bytes()).unwrap(),
+            crate::parser::parse_day_ahead_prices(xml).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_pretty_print_malformed() {
+        for xml in [&b"<a><b></a>"[..], b"<a><b>", b"<a attr=\"x></a>"] {
+            assert!(
+                matches!(pretty_print_xml(xml), Err(EntsoeError::XmlParseError(_))),
+                "{}",
+                String::from_utf8_lossy(xml)
+            );
+        }
+    }
+}